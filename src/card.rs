@@ -0,0 +1,263 @@
+use std::str::FromStr;
+
+// --- CARTAS ---
+
+// Rango de una carta, de menor a mayor
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Rank {
+    Two,
+    Three,
+    Four,
+    Five,
+    Six,
+    Seven,
+    Eight,
+    Nine,
+    Ten,
+    Jack,
+    Queen,
+    King,
+    Ace,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct MyCard {
+    pub rank: Rank,
+    pub suit: Suit,
+}
+
+impl MyCard {
+    pub fn new(rank: Rank, suit: Suit) -> Self {
+        Self { rank, suit }
+    }
+
+    // Posición única 0..52 (rango en orden de la matriz * 4 + palo)
+    pub fn index(self) -> usize {
+        // Sin búsquedas: se llama en todos los bucles calientes (CardSet)
+        let r = match self.rank {
+            Rank::Ace => 0, Rank::King => 1, Rank::Queen => 2, Rank::Jack => 3,
+            Rank::Ten => 4, Rank::Nine => 5, Rank::Eight => 6, Rank::Seven => 7,
+            Rank::Six => 8, Rank::Five => 9, Rank::Four => 10, Rank::Three => 11,
+            Rank::Two => 12,
+        };
+        let s = match self.suit {
+            Suit::Clubs => 0,
+            Suit::Diamonds => 1,
+            Suit::Hearts => 2,
+            Suit::Spades => 3,
+        };
+        r * 4 + s
+    }
+
+    // Inversa de `index`
+    pub fn from_index(index: usize) -> Self {
+        Self::new(RANKS[index / 4], SUITS[index % 4])
+    }
+
+    pub fn rank_char(&self) -> char {
+        match self.rank {
+            Rank::Two => '2', Rank::Three => '3', Rank::Four => '4', Rank::Five => '5',
+            Rank::Six => '6', Rank::Seven => '7', Rank::Eight => '8', Rank::Nine => '9',
+            Rank::Ten => 'T', Rank::Jack => 'J', Rank::Queen => 'Q', Rank::King => 'K',
+            Rank::Ace => 'A',
+        }
+    }
+
+    pub fn suit_char(&self) -> char {
+        match self.suit {
+            Suit::Clubs => 'c',
+            Suit::Diamonds => 'd',
+            Suit::Hearts => 'h',
+            Suit::Spades => 's',
+        }
+    }
+
+    pub fn display_text(&self) -> String {
+        let r = match self.rank {
+            Rank::Ten => "10".to_string(),
+            _ => self.rank_char().to_string(),
+        };
+        let s = match self.suit {
+            Suit::Clubs => "♣",
+            Suit::Diamonds => "♦",
+            Suit::Hearts => "♥",
+            Suit::Spades => "♠",
+        };
+        format!("{}{}", r, s)
+    }
+}
+
+// Notación corta: rango + palo (ej: "Ah", "Td", "2c")
+impl FromStr for MyCard {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut chars = text.trim().chars();
+        let (Some(r), Some(s), None) = (chars.next(), chars.next(), chars.next()) else {
+            return Err(format!("Carta inválida: '{}'", text));
+        };
+        let rank = RANKS.into_iter().find(|rank| rank_to_str(*rank).starts_with(r.to_ascii_uppercase()));
+        let suit = match s.to_ascii_lowercase() {
+            'c' => Some(Suit::Clubs),
+            'd' => Some(Suit::Diamonds),
+            'h' => Some(Suit::Hearts),
+            's' => Some(Suit::Spades),
+            _ => None,
+        };
+        match (rank, suit) {
+            (Some(rank), Some(suit)) => Ok(MyCard::new(rank, suit)),
+            _ => Err(format!("Carta inválida: '{}'", text)),
+        }
+    }
+}
+
+// --- CONSTANTES DE LA MATRIZ ---
+
+// Orden visual de la matriz: filas/columnas de As a Dos
+pub const RANKS: [Rank; 13] = [
+    Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten,
+    Rank::Nine, Rank::Eight, Rank::Seven, Rank::Six,
+    Rank::Five, Rank::Four, Rank::Three, Rank::Two
+];
+
+pub const SUITS: [Suit; 4] = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
+
+pub fn rank_to_str(r: Rank) -> &'static str {
+    match r {
+        Rank::Ace => "A", Rank::King => "K", Rank::Queen => "Q", Rank::Jack => "J",
+        Rank::Ten => "T", Rank::Nine => "9", Rank::Eight => "8", Rank::Seven => "7",
+        Rank::Six => "6", Rank::Five => "5", Rank::Four => "4", Rank::Three => "3",
+        Rank::Two => "2",
+    }
+}
+
+// Nombre de la celda (ej: AKs, 77, QJo).
+// Convención visual: r1 filas, r2 columnas. Triángulo superior = suited, inferior = offsuit.
+pub fn cell_name(r1_idx: usize, r2_idx: usize) -> String {
+    let (rank_a, rank_b) = if r1_idx <= r2_idx { (RANKS[r1_idx], RANKS[r2_idx]) } else { (RANKS[r2_idx], RANKS[r1_idx]) };
+    let suffix = if r1_idx == r2_idx { "" } else if r1_idx < r2_idx { "s" } else { "o" };
+    format!("{}{}{}", rank_to_str(rank_a), rank_to_str(rank_b), suffix)
+}
+
+// Combos reales de una celda: 6 para pares, 4 suited, 12 offsuit.
+pub fn cell_combos(r1_idx: usize, r2_idx: usize) -> Vec<[MyCard; 2]> {
+    let is_pair = r1_idx == r2_idx;
+    let is_suited = r1_idx < r2_idx;
+    let (r1, r2) = (RANKS[r1_idx], RANKS[r2_idx]);
+
+    let mut combos = Vec::with_capacity(12);
+    for (i1, s1) in SUITS.iter().enumerate() {
+        for (i2, s2) in SUITS.iter().enumerate() {
+            // Filtrar combos inválidos para la celda
+            if is_pair && i1 >= i2 { continue; } // AA no puede ser AhAh, y AhAd == AdAh
+            if is_suited && s1 != s2 { continue; }
+            if !is_suited && !is_pair && s1 == s2 { continue; }
+            combos.push([MyCard::new(r1, *s1), MyCard::new(r2, *s2)]);
+        }
+    }
+    combos
+}
+
+// Celda de la matriz (r1_idx, r2_idx) a la que pertenece un combo
+pub fn combo_cell(combo: &[MyCard; 2]) -> (usize, usize) {
+    let (a, b) = (combo[0].index() / 4, combo[1].index() / 4);
+    let (high, low) = (a.min(b), a.max(b));
+    if combo[0].suit == combo[1].suit { (high, low) } else { (low, high) }
+}
+
+// Clave de un combo independiente del orden de las cartas
+pub fn combo_key(combo: &[MyCard; 2]) -> (usize, usize) {
+    let (a, b) = (combo[0].index(), combo[1].index());
+    (a.min(b), a.max(b))
+}
+
+// --- CONJUNTOS DE CARTAS ---
+
+// Conjunto de cartas como máscara de 52 bits (bit = MyCard::index).
+// Bloqueos y mazo restante se resuelven con operaciones de bits en vez de hashes.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    const ALL: u64 = (1 << 52) - 1;
+
+    pub fn empty() -> Self {
+        Self(0)
+    }
+
+    pub fn full() -> Self {
+        Self(Self::ALL)
+    }
+
+    pub fn insert(&mut self, card: MyCard) {
+        self.0 |= 1 << card.index();
+    }
+
+    pub fn contains(self, card: MyCard) -> bool {
+        self.0 & (1 << card.index()) != 0
+    }
+
+    // true si comparten al menos una carta
+    pub fn intersects(self, other: CardSet) -> bool {
+        self.0 & other.0 != 0
+    }
+
+    pub fn union(self, other: CardSet) -> CardSet {
+        Self(self.0 | other.0)
+    }
+
+    // Cartas de la baraja que NO están en el conjunto (el mazo restante)
+    pub fn complement(self) -> CardSet {
+        Self(!self.0 & Self::ALL)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    // Cartas en orden de índice (rango de As a Dos, y palo)
+    pub fn iter(self) -> impl Iterator<Item = MyCard> {
+        self.indices().map(MyCard::from_index)
+    }
+
+    // Índices (MyCard::index) de las cartas, de menor a mayor
+    pub fn indices(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let index = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(index)
+        })
+    }
+}
+
+impl FromIterator<MyCard> for CardSet {
+    fn from_iter<I: IntoIterator<Item = MyCard>>(iter: I) -> Self {
+        let mut set = CardSet::empty();
+        for card in iter {
+            set.insert(card);
+        }
+        set
+    }
+}
+
+impl From<&[MyCard; 2]> for CardSet {
+    fn from(combo: &[MyCard; 2]) -> Self {
+        combo.iter().copied().collect()
+    }
+}
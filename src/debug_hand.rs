@@ -1,32 +1,58 @@
-use poker::{Card, Evaluator, Rank, Suit};
+use poker_solver::{describe, CalcMode, CardSet, EquityEngine, Language, MyCard, Range, Rank, Spot, Suit};
 
 fn main() {
-    let eval = Evaluator::new();
+    let engine = EquityEngine::new();
 
     // HERO: QQ (ej: Qh Qd)
-    let h1 = Card::new(Rank::Queen, Suit::Hearts);
-    let h2 = Card::new(Rank::Queen, Suit::Diamonds);
+    let hero = vec![MyCard::new(Rank::Queen, Suit::Hearts), MyCard::new(Rank::Queen, Suit::Diamonds)];
 
     // BOARD: As 8d Qc (Ap 8D Qt) -> As 8d Qc
-    let b1 = Card::new(Rank::Ace, Suit::Spades);
-    let b2 = Card::new(Rank::Eight, Suit::Diamonds);
-    let b3 = Card::new(Rank::Queen, Suit::Clubs);
-
-    let mut hand = vec![h1, h2, b1, b2, b3];
-    let score = eval.evaluate(&hand).unwrap();
-    println!("Hero Score (Raw): {} - Str: {}", score, score);
+    let board = vec![
+        MyCard::new(Rank::Ace, Suit::Spades),
+        MyCard::new(Rank::Eight, Suit::Diamonds),
+        MyCard::new(Rank::Queen, Suit::Clubs),
+    ];
 
     // Comparar con AA (Ah Ad)
-    let v1 = Card::new(Rank::Ace, Suit::Hearts);
-    let v2 = Card::new(Rank::Ace, Suit::Diamonds);
-    let v_hand = vec![v1, v2, b1, b2, b3];
-    let v_score = eval.evaluate(&v_hand).unwrap();
-    println!("Villain Score (Raw): {} - Str: {}", v_score, v_score);
+    let villain = vec![MyCard::new(Rank::Ace, Suit::Hearts), MyCard::new(Rank::Ace, Suit::Diamonds)];
+
+    let with_board = |hole: &[MyCard]| hole.iter().chain(board.iter()).copied().collect::<CardSet>();
+    let score = engine.rank(with_board(&hero));
+    println!("Hero Score (Raw): {:?} - Str: {}", score, describe(score, Language::Spanish));
+    let v_score = engine.rank(with_board(&villain));
+    println!("Villain Score (Raw): {:?} - Str: {}", v_score, describe(v_score, Language::Spanish));
 
+    let spot = Spot {
+        dead: hero.iter().chain(villain.iter()).chain(board.iter()).copied().collect(),
+        hero,
+        villain,
+        board,
+        range: Range::full(),
+        others: Vec::new(),
+    };
+    let duel = engine.duel(&[spot.hero[0], spot.hero[1]], &[spot.villain[0], spot.villain[1]], &spot.board, CardSet::empty());
+    let report = engine.calculate(&spot, CalcMode::Exhaustive);
 
-    if score < v_score {
-        println!("Hero wins!");
-    } else {
-        println!("Villain wins!");
+    // Equity real contando todos los turn/river posibles
+    println!(
+        "Hero equity: {:.2}% (win {:.2}% / lose {:.2}% / tie {:.2}%)",
+        report.totals.equity() * 100.0,
+        report.totals.win_pct(),
+        report.totals.lose_pct(),
+        report.totals.tie_pct()
+    );
+
+    // Duelo por turn: cartas con las que el hero pasa a ser favorito
+    println!("Duelo exacto: {:.2}% sobre {:.0} runouts", duel.totals.equity() * 100.0, duel.totals.total());
+    for (card, outcome) in duel.next_cards.iter().filter(|(_, o)| o.equity() > 0.5) {
+        println!("  Turn {}: {:.1}%", card.display_text(), outcome.equity() * 100.0);
     }
+
+    // Misma mano por muestreo (Monte Carlo, semilla fija)
+    let mc = engine.calculate(&spot, CalcMode::MonteCarlo { iterations: 20_000, seed: 7 });
+    println!(
+        "Monte Carlo equity: {:.2}% ± {:.2}%",
+        mc.totals.equity() * 100.0,
+        mc.std_error.unwrap_or(0.0) * 100.0
+    );
 }
@@ -0,0 +1,67 @@
+use crate::evaluator::{HandCategory, HandRank};
+
+// --- DESCRIPCIÓN DE LA MANO EN TEXTO ---
+//
+// Texto legible a partir del valor del evaluador (ej: "Trío de Damas, kickers A-9",
+// "Color al As (K-9-7-4)", "Doble pareja, Ases y Ochos, kicker K").
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    Spanish,
+    English,
+}
+
+// Por rango (2 = 0 ... A = 12): singular y plural
+const ES_NAMES: [(&str, &str); 13] = [
+    ("Dos", "Doses"), ("Tres", "Treses"), ("Cuatro", "Cuatros"), ("Cinco", "Cincos"), ("Seis", "Seises"),
+    ("Siete", "Sietes"), ("Ocho", "Ochos"), ("Nueve", "Nueves"), ("Diez", "Dieces"), ("Jota", "Jotas"),
+    ("Dama", "Damas"), ("Rey", "Reyes"), ("As", "Ases"),
+];
+const EN_NAMES: [(&str, &str); 13] = [
+    ("Two", "Twos"), ("Three", "Threes"), ("Four", "Fours"), ("Five", "Fives"), ("Six", "Sixes"),
+    ("Seven", "Sevens"), ("Eight", "Eights"), ("Nine", "Nines"), ("Ten", "Tens"), ("Jack", "Jacks"),
+    ("Queen", "Queens"), ("King", "Kings"), ("Ace", "Aces"),
+];
+const RANK_CHARS: [char; 13] = ['2', '3', '4', '5', '6', '7', '8', '9', 'T', 'J', 'Q', 'K', 'A'];
+
+pub fn describe(rank: HandRank, language: Language) -> String {
+    let r = rank.ranks().map(usize::from);
+    let kickers = |from: usize, count: usize| r[from..from + count].iter().map(|&k| RANK_CHARS[k].to_string()).collect::<Vec<_>>().join("-");
+    match language {
+        Language::Spanish => {
+            let one = |k: usize| ES_NAMES[k].0;
+            let many = |k: usize| ES_NAMES[k].1;
+            // "al As" / "a la Dama"
+            let to = |k: usize| if k == 9 || k == 10 { format!("a la {}", one(k)) } else { format!("al {}", one(k)) };
+            match rank.category() {
+                HandCategory::HighCard => format!("Carta alta: {}, kickers {}", one(r[0]), kickers(1, 4)),
+                HandCategory::Pair => format!("Pareja de {}, kickers {}", many(r[0]), kickers(1, 3)),
+                HandCategory::TwoPair => format!("Doble pareja, {} y {}, kicker {}", many(r[0]), many(r[1]), kickers(2, 1)),
+                HandCategory::ThreeOfAKind => format!("Trío de {}, kickers {}", many(r[0]), kickers(1, 2)),
+                HandCategory::Straight => format!("Escalera {}", to(r[0])),
+                HandCategory::Flush => format!("Color {} ({})", to(r[0]), kickers(1, 4)),
+                HandCategory::FullHouse => format!("Full de {} y {}", many(r[0]), many(r[1])),
+                HandCategory::FourOfAKind => format!("Póker de {}, kicker {}", many(r[0]), kickers(1, 1)),
+                HandCategory::StraightFlush if r[0] == 12 => "Escalera real".to_string(),
+                HandCategory::StraightFlush => format!("Escalera de color {}", to(r[0])),
+            }
+        }
+        Language::English => {
+            let one = |k: usize| EN_NAMES[k].0;
+            let many = |k: usize| EN_NAMES[k].1;
+            match rank.category() {
+                HandCategory::HighCard => format!("High card, {}, {} kickers", one(r[0]), kickers(1, 4)),
+                HandCategory::Pair => format!("Pair of {}, {} kickers", many(r[0]), kickers(1, 3)),
+                HandCategory::TwoPair => format!("Two pair, {} and {}, {} kicker", many(r[0]), many(r[1]), kickers(2, 1)),
+                HandCategory::ThreeOfAKind => format!("Three of a kind, {}, {} kickers", many(r[0]), kickers(1, 2)),
+                HandCategory::Straight => format!("Straight, {} high", one(r[0])),
+                HandCategory::Flush => format!("Flush, {} high ({})", one(r[0]), kickers(1, 4)),
+                HandCategory::FullHouse => format!("Full house, {} full of {}", many(r[0]), many(r[1])),
+                HandCategory::FourOfAKind => format!("Four of a kind, {}, {} kicker", many(r[0]), kickers(1, 1)),
+                HandCategory::StraightFlush if r[0] == 12 => "Royal flush".to_string(),
+                HandCategory::StraightFlush => format!("Straight flush, {} high", one(r[0])),
+            }
+        }
+    }
+}
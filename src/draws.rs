@@ -0,0 +1,153 @@
+use crate::card::MyCard;
+use crate::evaluator::straight_high;
+
+// --- PROYECTOS (DRAWS) ---
+//
+// Clasifica una mano de 2 cartas en flop o turn: proyectos de color y de escalera, backdoors
+// (sólo en el flop) y overcards. Un proyecto cuenta sólo si usa al menos una carta de la mano.
+// Máscaras de rangos de 13 bits como en el evaluador (bit 0 = Dos, bit 12 = As).
+
+// Tipo de proyecto (para filtrar la matriz)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum DrawKind {
+    FlushDraw,
+    NutFlushDraw,
+    OpenEnded,
+    Gutshot,
+    Backdoor,
+    Overcards,
+    ComboDraw,
+}
+
+impl DrawKind {
+    pub const ALL: [DrawKind; 7] = [
+        DrawKind::FlushDraw, DrawKind::NutFlushDraw, DrawKind::OpenEnded, DrawKind::Gutshot,
+        DrawKind::Backdoor, DrawKind::Overcards, DrawKind::ComboDraw,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DrawKind::FlushDraw => "Proyecto de color",
+            DrawKind::NutFlushDraw => "Proyecto de color nuts",
+            DrawKind::OpenEnded => "Escalera abierta",
+            DrawKind::Gutshot => "Gutshot",
+            DrawKind::Backdoor => "Backdoor",
+            DrawKind::Overcards => "Overcards",
+            DrawKind::ComboDraw => "Combo draw",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Draws {
+    pub flush_draw: bool,        // 4 cartas de un palo (sin color hecho)
+    pub nut_flush_draw: bool,    // ... y con la carta más alta que falta de ese palo
+    pub open_ended: bool,        // 2+ rangos completan escalera (incluye doble gutshot)
+    pub gutshot: bool,           // 1 rango completa escalera
+    pub backdoor_flush: bool,    // Flop: 3 cartas de un palo
+    pub backdoor_straight: bool, // Flop: turn + river completan escalera
+    pub overcards: u8,           // Cartas de la mano por encima de toda la mesa (sin pareja)
+}
+
+impl Draws {
+    // Proyectos de `hole` con la mesa (vacío preflop y en el river)
+    pub fn classify(hole: &[MyCard; 2], board: &[MyCard]) -> Draws {
+        let mut draws = Draws::default();
+        if !(3..=4).contains(&board.len()) {
+            return draws;
+        }
+        let rank = |c: &MyCard| 12 - c.index() / 4;
+        let suit = |c: &MyCard| c.index() % 4;
+        let mask = |cards: &mut dyn Iterator<Item = &MyCard>| cards.fold(0u16, |m, c| m | 1 << rank(c));
+        let board_mask = mask(&mut board.iter());
+        let all_mask = mask(&mut hole.iter().chain(board));
+
+        // Color
+        for s in 0..4 {
+            let in_hole = hole.iter().filter(|c| suit(c) == s).count();
+            let total = in_hole + board.iter().filter(|c| suit(c) == s).count();
+            if in_hole == 0 {
+                continue;
+            }
+            if total == 4 {
+                draws.flush_draw = true;
+                // Nuts: la mano tiene el rango más alto del palo que no está en la mesa
+                let suited_board = mask(&mut board.iter().filter(|c| suit(c) == s));
+                let nut = (0..13).rev().find(|r| suited_board & (1 << r) == 0);
+                draws.nut_flush_draw |= hole.iter().any(|c| suit(c) == s && Some(rank(c)) == nut);
+            } else if total == 3 && board.len() == 3 {
+                draws.backdoor_flush = true;
+            }
+        }
+
+        // Escalera: rangos que la completan usando la mano (mejor que la que haría la mesa sola)
+        if straight_high(all_mask).is_none() {
+            let completes = |extra: u16| straight_high(all_mask | extra) > straight_high(board_mask | extra);
+            let outs = (0..13).filter(|r| all_mask & (1 << r) == 0 && completes(1 << r)).count();
+            draws.open_ended = outs >= 2;
+            draws.gutshot = outs == 1;
+            if outs == 0 && board.len() == 3 {
+                draws.backdoor_straight = (0..13).any(|a| (a + 1..13).any(|b| completes(1 << a | 1 << b)));
+            }
+        }
+
+        // Overcards: mano sin pareja por encima de la carta más alta de la mesa
+        let board_high = 15 - board_mask.leading_zeros() as usize;
+        let paired = rank(&hole[0]) == rank(&hole[1]) || hole.iter().any(|c| board_mask & (1 << rank(c)) != 0);
+        if !paired {
+            draws.overcards = hole.iter().filter(|c| rank(c) > board_high).count() as u8;
+        }
+        draws
+    }
+
+    pub fn is_combo_draw(&self) -> bool {
+        self.flush_draw && (self.open_ended || self.gutshot)
+    }
+
+    pub fn has(&self, kind: DrawKind) -> bool {
+        match kind {
+            DrawKind::FlushDraw => self.flush_draw,
+            DrawKind::NutFlushDraw => self.nut_flush_draw,
+            DrawKind::OpenEnded => self.open_ended,
+            DrawKind::Gutshot => self.gutshot,
+            DrawKind::Backdoor => self.backdoor_flush || self.backdoor_straight,
+            DrawKind::Overcards => self.overcards > 0,
+            DrawKind::ComboDraw => self.is_combo_draw(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Draws::default()
+    }
+
+    // Descripción corta (ej: "Combo draw: Proyecto de color nuts + Escalera abierta")
+    pub fn labels(&self) -> Vec<String> {
+        let mut labels = Vec::new();
+        if self.is_combo_draw() {
+            labels.push(DrawKind::ComboDraw.name().to_string());
+        }
+        if self.nut_flush_draw {
+            labels.push(DrawKind::NutFlushDraw.name().to_string());
+        } else if self.flush_draw {
+            labels.push(DrawKind::FlushDraw.name().to_string());
+        }
+        if self.open_ended {
+            labels.push(DrawKind::OpenEnded.name().to_string());
+        }
+        if self.gutshot {
+            labels.push(DrawKind::Gutshot.name().to_string());
+        }
+        if self.backdoor_flush {
+            labels.push("Backdoor de color".to_string());
+        }
+        if self.backdoor_straight {
+            labels.push("Backdoor de escalera".to_string());
+        }
+        match self.overcards {
+            0 => {}
+            1 => labels.push("1 overcard".to_string()),
+            n => labels.push(format!("{} overcards", n)),
+        }
+        labels
+    }
+}
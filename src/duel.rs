@@ -0,0 +1,49 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::{combinations, EquityEngine, Outcome};
+
+// --- DUELO 1 VS 1: RESULTADO POR CARTA ---
+
+// Duelo con las dos manos a la vista, recorriendo todos los runouts.
+// Los contadores son runouts (no se normalizan): en el flop hay 990, en el turn 44.
+#[derive(Clone, Debug, Default)]
+pub struct DuelReport {
+    pub totals: Outcome,
+    // Por cada carta que puede salir a continuación (turn en el flop, river en el turn):
+    // resultado de todos los runouts que la incluyen
+    pub next_cards: Vec<(MyCard, Outcome)>,
+}
+
+impl EquityEngine {
+    // Pensado para flop y turn (preflop serían 1,7 millones de runouts por carta).
+    // `dead` = cartas fuera del mazo además de las dos manos y la mesa.
+    pub fn duel(&self, hero: &[MyCard; 2], villain: &[MyCard; 2], board: &[MyCard], dead: CardSet) -> DuelReport {
+        let board_set: CardSet = board.iter().copied().collect();
+        let known = dead.union(board_set).union(CardSet::from(hero)).union(CardSet::from(villain));
+        let deck: Vec<MyCard> = known.complement().iter().collect();
+        let missing = 5 - board.len().min(5);
+        let settle = |full_board: CardSet, outcome: &mut Outcome| {
+            outcome.record(self.rank(full_board.union(CardSet::from(hero))), self.rank(full_board.union(CardSet::from(villain))));
+        };
+
+        let mut report = DuelReport::default();
+        if missing == 0 {
+            settle(board_set, &mut report.totals);
+            return report;
+        }
+        for (i, &card) in deck.iter().enumerate() {
+            let mut outcome = Outcome::default();
+            let rest: Vec<MyCard> = deck.iter().enumerate().filter(|(j, _)| *j != i).map(|(_, c)| *c).collect();
+            for extra in combinations(&rest, missing - 1) {
+                let mut full_board = board_set;
+                full_board.insert(card);
+                extra.into_iter().for_each(|c| full_board.insert(c));
+                settle(full_board, &mut outcome);
+            }
+            report.totals.add(&outcome);
+            report.next_cards.push((card, outcome));
+        }
+        // Cada runout aparece una vez por cada carta que incluye
+        report.totals = report.totals.scaled(1.0 / missing as f64);
+        report
+    }
+}
@@ -0,0 +1,1006 @@
+use crate::card::{cell_combos, combo_key, CardSet, MyCard};
+use crate::evaluator::{HandEvaluator, HandRank};
+use crate::iso::{self, SuitPermutation};
+use crate::parallel;
+use crate::preflop::PreflopRow;
+use crate::range::Range;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+
+// --- ENTRADA ---
+
+// Situación a evaluar: cartas del hero, villano opcional, mesa y cartas muertas.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Spot {
+    pub hero: Vec<MyCard>,
+    pub villain: Vec<MyCard>,   // Vacío = rival con cualquier mano
+    pub board: Vec<MyCard>,
+    pub dead: CardSet,          // Todas las cartas conocidas (hero, villano, aliados, mesa)
+    pub range: Range,           // Rango del villano (matriz) cuando no hay villano concreto
+    pub others: Vec<Range>,     // Rivales extra (multiway), cada uno con su rango
+}
+
+impl Spot {
+    pub fn is_1v1(&self) -> bool {
+        self.villain.len() == 2 && self.others.is_empty()
+    }
+
+    // Rivales en la mano (el villano principal + los extra)
+    pub fn opponents(&self) -> usize {
+        1 + self.others.len()
+    }
+
+    fn is_blocked(&self, combo: &[MyCard; 2]) -> bool {
+        self.dead.intersects(CardSet::from(combo))
+    }
+
+    // Combos del rango (con su peso) que el villano todavía puede tener
+    pub fn live_range_combos(&self) -> Vec<([MyCard; 2], f64)> {
+        self.range.weighted_combos().into_iter().filter(|(c, _)| !self.is_blocked(c)).collect()
+    }
+
+    // Preflop sin más cartas muertas que las del hero y el villano: vale la tabla exacta (ver preflop.rs)
+    fn uses_preflop_table(&self) -> bool {
+        let hands: CardSet = self.hero.iter().chain(&self.villain).copied().collect();
+        self.board.is_empty() && self.dead == hands
+    }
+
+    // Cartas que todavía pueden salir en turn/river
+    fn remaining_deck(&self) -> Vec<MyCard> {
+        self.dead.complement().iter().collect()
+    }
+}
+
+// Cómo se recorren los runouts (y las manos rivales)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CalcMode {
+    // Todos los turn/river posibles contra todos los combos
+    #[default]
+    Exhaustive,
+    // Reparte `iterations` runouts (y combos rivales) al azar. Misma semilla = mismo resultado.
+    MonteCarlo { iterations: u32, seed: u64 },
+}
+
+// --- RESULTADOS ---
+
+// Reparto de equity desde el punto de vista del hero.
+// Cada mano rival aporta 1.0 repartido entre victoria/derrota/empate según los runouts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Outcome {
+    pub wins: f64,   // Manos que YO gano
+    pub losses: f64, // Manos que ME ganan
+    pub ties: f64,
+    // Parte del bote que se lleva el hero en los empates (1/2 en un duelo, 1/3 a tres bandas...)
+    pub tie_share: f64,
+}
+
+impl Outcome {
+    pub fn total(&self) -> f64 {
+        self.wins + self.losses + self.ties
+    }
+
+    pub fn win_pct(&self) -> f32 {
+        pct(self.wins, self.total())
+    }
+
+    pub fn lose_pct(&self) -> f32 {
+        pct(self.losses, self.total())
+    }
+
+    pub fn tie_pct(&self) -> f32 {
+        pct(self.ties, self.total())
+    }
+
+    // Equity del hero: victorias + su parte de los empates
+    pub fn equity(&self) -> f64 {
+        let total = self.total();
+        if total == 0.0 { 0.0 } else { (self.wins + self.tie_share) / total }
+    }
+
+    // `hero` comparado contra `villain`: HandRank mayor = mano mejor
+    pub(crate) fn record(&mut self, hero: HandRank, villain: HandRank) {
+        self.record_weighted(hero, villain, 1.0);
+    }
+
+    pub(crate) fn record_weighted(&mut self, hero: HandRank, villain: HandRank, weight: f64) {
+        match hero.cmp(&villain) {
+            Ordering::Greater => self.wins += weight,
+            Ordering::Less => self.losses += weight,
+            Ordering::Equal => {
+                self.ties += weight;
+                self.tie_share += weight / 2.0;
+            }
+        }
+    }
+
+    // Hero contra la mejor mano rival; `tied` = rivales que tienen esa mejor mano
+    fn record_multiway(&mut self, hero: HandRank, best: HandRank, tied: usize) {
+        match hero.cmp(&best) {
+            Ordering::Greater => self.wins += 1.0,
+            Ordering::Less => self.losses += 1.0,
+            Ordering::Equal => {
+                self.ties += 1.0;
+                self.tie_share += 1.0 / (1 + tied) as f64;
+            }
+        }
+    }
+
+    pub(crate) fn add(&mut self, other: &Outcome) {
+        self.wins += other.wins;
+        self.losses += other.losses;
+        self.ties += other.ties;
+        self.tie_share += other.tie_share;
+    }
+
+    // Normaliza a una sola mano (wins + losses + ties == 1.0)
+    pub(crate) fn normalized(&self) -> Outcome {
+        let total = self.total();
+        if total == 0.0 {
+            return Outcome::default();
+        }
+        self.scaled(1.0 / total)
+    }
+
+    pub(crate) fn scaled(&self, factor: f64) -> Outcome {
+        Outcome {
+            wins: self.wins * factor,
+            losses: self.losses * factor,
+            ties: self.ties * factor,
+            tie_share: self.tie_share * factor,
+        }
+    }
+}
+
+// Acumula muestras de equity (1, parte del bote o 0) para estimar el error estándar
+#[derive(Default)]
+struct Sampler {
+    n: f64,
+    sum: f64,
+    sum_sq: f64,
+}
+
+impl Sampler {
+    // Parte del bote del hero contra la mejor mano rival (`tied` rivales tienen esa mejor mano)
+    fn push(&mut self, hero: HandRank, best: HandRank, tied: usize) {
+        self.push_value(match hero.cmp(&best) {
+            Ordering::Greater => 1.0,
+            Ordering::Less => 0.0,
+            Ordering::Equal => 1.0 / (1 + tied) as f64,
+        });
+    }
+
+    fn push_value(&mut self, x: f64) {
+        self.n += 1.0;
+        self.sum += x;
+        self.sum_sq += x * x;
+    }
+
+    fn merge(&mut self, other: &Sampler) {
+        self.n += other.n;
+        self.sum += other.sum;
+        self.sum_sq += other.sum_sq;
+    }
+
+    fn std_error(&self) -> f64 {
+        if self.n < 2.0 {
+            return 0.0;
+        }
+        let mean = self.sum / self.n;
+        let variance = (self.sum_sq / self.n - mean * mean).max(0.0);
+        (variance / self.n).sqrt()
+    }
+}
+
+fn pct(part: f64, total: f64) -> f32 {
+    if total == 0.0 { 0.0 } else { (part / total * 100.0) as f32 }
+}
+
+// Estado de una celda de la matriz 13x13
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CellStats {
+    pub total_combos: u32,
+    pub blocked: u32,   // Combos imposibles por cartas conocidas
+    pub outcome: Outcome, // Ponderado por el peso de la celda en el rango
+    // Combos donde el hero va por delante / por detrás / igualado en equity
+    pub ahead: u32,
+    pub behind: u32,
+    pub even: u32,
+}
+
+impl CellStats {
+    pub fn is_fully_blocked(&self) -> bool {
+        self.blocked == self.total_combos && self.total_combos > 0
+    }
+
+    fn record_combo(&mut self, outcome: Outcome, weight: f64) {
+        match outcome.wins.partial_cmp(&outcome.losses) {
+            Some(Ordering::Greater) => self.ahead += 1,
+            Some(Ordering::Less) => self.behind += 1,
+            _ => self.even += 1,
+        }
+        self.outcome.add(&outcome.scaled(weight));
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct EquityReport {
+    pub is_1v1: bool,
+    pub opponents: usize, // Rivales en la mano (1 = heads-up)
+    // En 1vs1 es el duelo contra el villano; si no, contra todas las manos posibles
+    // (en multiway, victoria = ganar solo y empate = repartir con al menos un rival)
+    pub totals: Outcome,
+    pub cells: [[CellStats; 13]; 13],
+    // Equity del hero contra cada combo posible del rango (normalizada, sin peso), por combo_key
+    pub combo_outcomes: HashMap<(usize, usize), Outcome>,
+    pub range_combos: f64, // Combos posibles que quedan en el rango del villano (ponderados)
+    // Sólo si se muestrearon runouts (Monte Carlo o preflop): error estándar de la equity (0.0..1.0)
+    pub std_error: Option<f64>,
+}
+
+impl EquityReport {
+    // Mismo resultado para el Spot con los palos cambiados. Celdas y totales no dependen de los palos;
+    // sólo cambian las claves de los combos.
+    pub fn permuted(&self, perm: SuitPermutation) -> EquityReport {
+        if perm.is_identity() {
+            return self.clone();
+        }
+        let combo_outcomes = self
+            .combo_outcomes
+            .iter()
+            .map(|(&(a, b), outcome)| {
+                let (pa, pb) = (perm.apply_index(a), perm.apply_index(b));
+                ((pa.min(pb), pa.max(pb)), *outcome)
+            })
+            .collect();
+        EquityReport { combo_outcomes, ..self.clone() }
+    }
+}
+
+// Progreso y cancelación de un cálculo, compartido entre el hilo que calcula y la GUI
+#[derive(Debug, Default)]
+pub struct Progress {
+    cancelled: AtomicBool,
+    done: AtomicUsize,
+    total: AtomicUsize,
+}
+
+impl Progress {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, AtomicOrdering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(AtomicOrdering::Relaxed)
+    }
+
+    // 0.0..=1.0
+    pub fn fraction(&self) -> f32 {
+        let total = self.total.load(AtomicOrdering::Relaxed);
+        if total == 0 { 0.0 } else { (self.done.load(AtomicOrdering::Relaxed) as f32 / total as f32).min(1.0) }
+    }
+
+    pub(crate) fn set_total(&self, total: usize) {
+        self.total.store(total, AtomicOrdering::Relaxed);
+    }
+
+    pub(crate) fn advance(&self, steps: usize) {
+        self.done.fetch_add(steps, AtomicOrdering::Relaxed);
+    }
+}
+
+// Resultado de recorrer la matriz: celdas, equity por combo y totales del rango
+struct MatrixStats {
+    cells: [[CellStats; 13]; 13],
+    combos: HashMap<(usize, usize), Outcome>,
+    range_totals: Outcome,
+}
+
+// Mesa completa (5 cartas) y la mano del hero evaluada sobre ella
+struct Runout {
+    board: CardSet,
+    extra: CardSet, // Cartas que se agregaron a la mesa actual
+    hero_score: HandRank,
+}
+
+// Antes del flop hay C(48,5) mesas posibles. Sin más cartas muertas que las manos en juego se usa la tabla
+// exacta (preflop.rs); con aliados u otras cartas muertas, en modo exhaustivo la matriz (y la equity contra
+// un rango) usa una muestra fija (misma semilla siempre, así el resultado no cambia entre recálculos).
+// Contra un villano concreto la equity del duelo sí recorre todas las mesas (ver `exact_duel`).
+pub const PREFLOP_BOARDS: usize = 2_000;
+pub(crate) const PREFLOP_SEED: u64 = 0x5eed;
+
+// Con 2+ rivales no se pueden recorrer todas las manos rivales: en modo exhaustivo se usa
+// Monte Carlo con estas iteraciones y la semilla fija de arriba
+pub const MULTIWAY_ITERATIONS: u32 = 50_000;
+// Intentos para repartir a la vez todas las manos rivales sin cartas repetidas
+const DEAL_ATTEMPTS: usize = 64;
+
+// Reparte un combo a cada asiento (`draw` sortea el combo de un asiento). Si dos manos chocan se
+// vuelve a repartir todo: rehacer sólo el asiento que choca sesgaría el reparto hacia los combos
+// que bloquean menos. Devuelve `None` si ningún intento salió sin choques.
+pub(crate) fn deal_hands(seats: usize, rng: &mut StdRng, mut draw: impl FnMut(usize, &mut StdRng) -> [MyCard; 2]) -> Option<(Vec<[MyCard; 2]>, CardSet)> {
+    let mut hands = Vec::with_capacity(seats);
+    'attempts: for _ in 0..DEAL_ATTEMPTS {
+        hands.clear();
+        let mut used = CardSet::empty();
+        for seat in 0..seats {
+            let combo = draw(seat, rng);
+            let cards = CardSet::from(&combo);
+            if used.intersects(cards) {
+                continue 'attempts;
+            }
+            used = used.union(cards);
+            hands.push(combo);
+        }
+        return Some((hands, used));
+    }
+    None
+}
+
+// Tamaño fijo de los bloques que se reparten entre hilos. No depende de la cantidad de hilos,
+// así las sumas parciales se combinan siempre en el mismo orden y el resultado es idéntico.
+const COMBO_CHUNK: usize = 8;
+const RUNOUT_CHUNK: usize = 64;
+pub(crate) const MC_BLOCK: u32 = 4_096;
+
+// Generador de un bloque Monte Carlo: depende sólo de la semilla y del número de bloque
+pub(crate) fn block_rng(seed: u64, block: usize) -> StdRng {
+    StdRng::seed_from_u64(seed ^ (block as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15))
+}
+
+// Resultado parcial de un bloque de iteraciones Monte Carlo
+#[derive(Default)]
+struct McBlock {
+    raw: Outcome,
+    sampler: Sampler,
+    per_combo: HashMap<(usize, usize), Outcome>,
+}
+
+// --- MOTOR ---
+
+pub struct EquityEngine {
+    hands: HandEvaluator, // Evaluador propio (runouts, descripciones, fuerza de la mano)
+    threads: AtomicUsize, // Hilos para evaluar combos/runouts (no cambia el resultado)
+}
+
+impl Default for EquityEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EquityEngine {
+    pub fn new() -> Self {
+        Self { hands: HandEvaluator::new(), threads: AtomicUsize::new(parallel::default_threads()) }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.threads.load(AtomicOrdering::Relaxed)
+    }
+
+    // Se puede cambiar en cualquier momento; aplica desde el próximo cálculo
+    pub fn set_threads(&self, threads: usize) {
+        self.threads.store(threads.max(1), AtomicOrdering::Relaxed);
+    }
+
+    // Valor de 5 a 7 cartas con el evaluador propio
+    pub fn rank(&self, cards: CardSet) -> HandRank {
+        self.hands.evaluate(cards)
+    }
+
+    fn score_runout(&self, hole: &[MyCard; 2], board: CardSet) -> HandRank {
+        self.hands.evaluate(board.union(CardSet::from(hole)))
+    }
+
+    // Todos los turn/river posibles, con la mano del hero ya evaluada.
+    // Sin flop (sólo con cartas muertas extra: si no, se usa la tabla) devuelve una muestra de PREFLOP_BOARDS mesas (segundo valor = true).
+    fn runouts(&self, spot: &Spot, hero: &[MyCard; 2]) -> (Vec<Runout>, bool) {
+        let missing = 5 - spot.board.len().min(5);
+        let mut deck = spot.remaining_deck();
+        let sampled = missing > 2;
+        let extras = if sampled {
+            let mut rng = StdRng::seed_from_u64(PREFLOP_SEED);
+            (0..PREFLOP_BOARDS).map(|_| deck.partial_shuffle(&mut rng, missing).0.to_vec()).collect()
+        } else {
+            combinations(&deck, missing)
+        };
+        let runouts = parallel::map_chunks(&extras, RUNOUT_CHUNK, self.threads(), |chunk| {
+            chunk
+                .iter()
+                .map(|extra| {
+                    let extra: CardSet = extra.iter().copied().collect();
+                    let board = extra.union(spot.board.iter().copied().collect());
+                    Runout { board, extra, hero_score: self.score_runout(hero, board) }
+                })
+                .collect::<Vec<_>>()
+        });
+        (runouts.into_iter().flatten().collect(), sampled)
+    }
+
+    // Equity del hero contra un combo concreto, promediando los runouts compatibles.
+    // `per_runout` acumula además el resultado de cada runout con el peso del combo (para el error estándar).
+    fn combo_outcome(&self, runouts: &[Runout], combo: &[MyCard; 2], weight: f64, per_runout: &mut [Outcome]) -> Outcome {
+        let mut outcome = Outcome::default();
+        for (runout, acc) in runouts.iter().zip(per_runout.iter_mut()) {
+            if runout.extra.intersects(CardSet::from(combo)) {
+                continue;
+            }
+            let v_score = self.score_runout(combo, runout.board);
+            outcome.record(runout.hero_score, v_score);
+            acc.record_weighted(runout.hero_score, v_score, weight);
+        }
+        outcome.normalized()
+    }
+
+    pub fn calculate(&self, spot: &Spot, mode: CalcMode) -> EquityReport {
+        self.calculate_with_progress(spot, mode, &Progress::default())
+            .expect("un cálculo sin cancelar siempre termina")
+    }
+
+    // Igual que `calculate`, pero informa el avance en `progress` y devuelve None si se canceló.
+    // Se calcula sobre la forma canónica del Spot: situaciones isomorfas dan exactamente el mismo resultado
+    // (también en Monte Carlo, porque los runouts se reparten sobre los mismos palos).
+    pub fn calculate_with_progress(&self, spot: &Spot, mode: CalcMode, progress: &Progress) -> Option<EquityReport> {
+        let (canonical, perm) = iso::canonical(spot);
+        let report = self.calculate_canonical(&canonical, mode, progress)?;
+        Some(report.permuted(perm.inverse()))
+    }
+
+    fn calculate_canonical(&self, spot: &Spot, mode: CalcMode, progress: &Progress) -> Option<EquityReport> {
+        // Sólo calculamos si el hero tiene sus 2 cartas (con o sin flop)
+        let hero = match spot.hero.as_slice() {
+            [h1, h2] => Some([*h1, *h2]),
+            _ => None,
+        };
+
+        let (matrix, totals, std_error) = match (hero, mode) {
+            (Some(hero), CalcMode::Exhaustive) if !spot.others.is_empty() => {
+                self.monte_carlo(spot, &hero, MULTIWAY_ITERATIONS, PREFLOP_SEED, progress)
+            }
+            (Some(hero), CalcMode::Exhaustive) => self.exhaustive(spot, &hero, progress),
+            (Some(hero), CalcMode::MonteCarlo { iterations, seed }) => self.monte_carlo(spot, &hero, iterations, seed, progress),
+            (None, _) => (build_cells(spot, |_, _| None), Outcome::default(), None),
+        };
+        if progress.is_cancelled() {
+            return None;
+        }
+
+        let range_combos = spot.live_range_combos().iter().map(|(_, w)| w).sum();
+        Some(EquityReport {
+            is_1v1: spot.is_1v1(),
+            opponents: spot.opponents(),
+            totals,
+            cells: matrix.cells,
+            combo_outcomes: matrix.combos,
+            range_combos,
+            std_error,
+        })
+    }
+
+    fn exhaustive(&self, spot: &Spot, hero: &[MyCard; 2], progress: &Progress) -> (MatrixStats, Outcome, Option<f64>) {
+        if spot.uses_preflop_table() {
+            return self.preflop_exact(spot, hero, progress);
+        }
+        let (runouts, sampled) = self.runouts(spot, hero);
+        let range_combos = spot.live_range_combos();
+        // Con todos los runouts, los combos que se transforman uno en otro con una simetría del Spot
+        // tienen la misma equity: calculamos sólo el menor de cada grupo. (Con mesas muestreadas la
+        // muestra no es simétrica, así que ahí se calculan todos.)
+        let symmetries = if sampled { vec![SuitPermutation::identity()] } else { iso::stabilizer(spot) };
+        let representative = |combo: &[MyCard; 2]| {
+            symmetries.iter().map(|perm| combo_key(&perm.apply_combo(combo))).min().expect("incluye la identidad")
+        };
+        let to_compute: Vec<([MyCard; 2], f64)> =
+            range_combos.iter().filter(|(combo, _)| representative(combo) == combo_key(combo)).copied().collect();
+
+        // Preflop contra un villano concreto: la muestra sólo sirve para la matriz, el duelo se recorre entero
+        let exact_villain = match (sampled, spot.villain.as_slice()) {
+            (true, [v1, v2]) => Some([*v1, *v2]),
+            _ => None,
+        };
+        // Un paso por combo a calcular (más uno por primera carta de la mesa en el duelo exacto)
+        let duel_steps = exact_villain.map_or(0, |_| spot.remaining_deck().len());
+        progress.set_total(to_compute.len() + duel_steps);
+        // Cada bloque de combos acumula su propio reparto por runout; después se suman en orden de bloque
+        let chunks = parallel::map_chunks(&to_compute, COMBO_CHUNK, self.threads(), |chunk| {
+            let mut by_runout = vec![Outcome::default(); runouts.len()];
+            let mut outcomes = Vec::with_capacity(chunk.len());
+            for (combo, weight) in chunk {
+                if progress.is_cancelled() {
+                    break;
+                }
+                progress.advance(1);
+                outcomes.push((combo_key(combo), self.combo_outcome(&runouts, combo, *weight, &mut by_runout)));
+            }
+            (outcomes, by_runout)
+        });
+
+        let mut range_by_runout = vec![Outcome::default(); runouts.len()];
+        let mut per_combo = HashMap::new();
+        for (outcomes, by_runout) in chunks {
+            per_combo.extend(outcomes);
+            for (acc, outcome) in range_by_runout.iter_mut().zip(&by_runout) {
+                acc.add(outcome);
+            }
+        }
+        let matrix = build_cells(spot, |combo, _| per_combo.get(&representative(combo)).copied());
+
+        let (totals, by_runout) = match (spot.villain.as_slice(), exact_villain) {
+            // CÁLCULO 1vs1 PREFLOP (todas las mesas)
+            (_, Some(villain)) => (self.exact_duel(spot, hero, &villain, progress), Vec::new()),
+            // CÁLCULO 1vs1
+            ([v1, v2], None) => {
+                let mut villain_by_runout = vec![Outcome::default(); runouts.len()];
+                (self.combo_outcome(&runouts, &[*v1, *v2], 1.0, &mut villain_by_runout), villain_by_runout)
+            }
+            // CÁLCULO VS RANGO (DECK RESTANTE)
+            _ => (matrix.range_totals, range_by_runout),
+        };
+
+        // Con mesas muestreadas, cada mesa es una observación independiente de la equity
+        let std_error = (sampled && exact_villain.is_none()).then(|| {
+            let mut sampler = Sampler::default();
+            for outcome in by_runout.iter().filter(|o| o.total() > 0.0) {
+                sampler.push_value(outcome.equity());
+            }
+            sampler.std_error()
+        });
+        (matrix, totals, std_error)
+    }
+
+    // Preflop sin cartas muertas extra: cada combo del villano sale de la tabla, sin recorrer mesas
+    fn preflop_exact(&self, spot: &Spot, hero: &[MyCard; 2], progress: &Progress) -> (MatrixStats, Outcome, Option<f64>) {
+        progress.set_total(1);
+        let row = PreflopRow::new(hero);
+        let matrix = build_cells(spot, |combo, _| row.outcome(combo));
+        let totals = match spot.villain.as_slice() {
+            [v1, v2] => row.outcome(&[*v1, *v2]).unwrap_or_default(),
+            _ => matrix.range_totals,
+        };
+        progress.advance(1);
+        (matrix, totals, None)
+    }
+
+    // Duelo preflop exacto: recorre todas las mesas posibles. Una tarea por primera carta de la mesa
+    // (el resto se enumera dentro), sumadas en orden como siempre.
+    fn exact_duel(&self, spot: &Spot, hero: &[MyCard; 2], villain: &[MyCard; 2], progress: &Progress) -> Outcome {
+        let missing = 5 - spot.board.len().min(5);
+        let deck = spot.remaining_deck();
+        let board: CardSet = spot.board.iter().copied().collect();
+        let (hero, villain) = (CardSet::from(hero), CardSet::from(villain));
+        let partials = parallel::map_indexed(deck.len(), self.threads(), |first| {
+            let mut outcome = Outcome::default();
+            if progress.is_cancelled() {
+                return outcome;
+            }
+            let mut start = board;
+            start.insert(deck[first]);
+            for_each_board(&deck[first + 1..], missing - 1, start, &mut |full| {
+                outcome.record(self.rank(full.union(hero)), self.rank(full.union(villain)));
+            });
+            progress.advance(1);
+            outcome
+        });
+        let mut totals = Outcome::default();
+        for outcome in &partials {
+            totals.add(outcome);
+        }
+        totals.normalized()
+    }
+
+    // Reparte runouts (y en modo rango también las manos rivales) al azar del mazo restante.
+    // Las iteraciones van en bloques de MC_BLOCK, cada uno con su propia semilla derivada de `seed`,
+    // así el resultado sólo depende de la semilla y no de cuántos hilos hay.
+    // Los rivales extra (multiway) reciben un combo de su rango sin repetir cartas ya repartidas.
+    fn monte_carlo(&self, spot: &Spot, hero: &[MyCard; 2], iterations: u32, seed: u64, progress: &Progress) -> (MatrixStats, Outcome, Option<f64>) {
+        let deck = spot.remaining_deck();
+        let villain = match spot.villain.as_slice() {
+            [v1, v2] => Some([*v1, *v2]),
+            _ => None,
+        };
+        let range_combos = spot.live_range_combos();
+        let range_weight: f64 = range_combos.iter().map(|(_, w)| w).sum();
+        // Los combos se reparten según su peso en el rango
+        let combo_dist = WeightedIndex::new(range_combos.iter().map(|(_, w)| *w)).ok();
+        let others = spot
+            .others
+            .iter()
+            .map(|range| {
+                let (combos, weights): (Vec<[MyCard; 2]>, Vec<f64>) =
+                    range.weighted_combos().into_iter().filter(|(c, _)| !spot.is_blocked(c)).unzip();
+                (combos, WeightedIndex::new(weights).ok())
+            })
+            .collect::<Vec<_>>();
+        let missing = 5 - spot.board.len().min(5);
+        // Repartimos cartas de más para poder saltear las de los combos rivales
+        let to_deal = missing + 2 * spot.opponents();
+
+        progress.set_total(iterations as usize);
+        let blocks = iterations.div_ceil(MC_BLOCK) as usize;
+        let results = parallel::map_indexed(blocks, self.threads(), |block| {
+            let mut result = McBlock::default();
+            let mut rng = block_rng(seed, block);
+            let mut deck = deck.clone();
+            let start = block as u32 * MC_BLOCK;
+            let end = (start + MC_BLOCK).min(iterations);
+
+            // Con algún rango vacío no hay repartos posibles
+            let empty_range = (villain.is_none() && combo_dist.is_none()) || others.iter().any(|(_, dist)| dist.is_none());
+            if deck.len() < to_deal || empty_range {
+                return result;
+            }
+            for i in start..end {
+                if (i - start).is_multiple_of(1024) {
+                    if progress.is_cancelled() {
+                        break;
+                    }
+                    progress.advance(1024.min((end - i) as usize));
+                }
+                let seats = 1 + others.len();
+                let Some((hands, used)) = deal_hands(seats, &mut rng, |seat, rng| match (seat, villain) {
+                    (0, Some(v)) => v,
+                    (0, None) => range_combos[combo_dist.as_ref().expect("rango no vacío").sample(rng)].0,
+                    (_, _) => {
+                        let (combos, dist) = &others[seat - 1];
+                        combos[dist.as_ref().expect("rango no vacío").sample(rng)]
+                    }
+                }) else {
+                    continue; // Ningún reparto sin choques en este intento
+                };
+                let (villain_cards, other_hands) = (hands[0], &hands[1..]);
+
+                let (dealt, _) = deck.partial_shuffle(&mut rng, to_deal);
+                let board: CardSet = spot.board.iter()
+                    .chain(dealt.iter().filter(|c| !used.contains(**c)).take(missing))
+                    .copied()
+                    .collect();
+
+                let h_score = self.score_runout(hero, board);
+                let mut best = self.score_runout(&villain_cards, board);
+                let mut tied = 1;
+                for combo in other_hands {
+                    let score = self.score_runout(combo, board);
+                    match score.cmp(&best) {
+                        Ordering::Greater => (best, tied) = (score, 1),
+                        Ordering::Equal => tied += 1,
+                        Ordering::Less => {}
+                    }
+                }
+                result.raw.record_multiway(h_score, best, tied);
+                result.sampler.push(h_score, best, tied);
+                if villain.is_none() {
+                    result.per_combo.entry(combo_key(&villain_cards)).or_default().record_multiway(h_score, best, tied);
+                }
+            }
+            result
+        });
+
+        let mut raw = Outcome::default();
+        let mut sampler = Sampler::default();
+        let mut per_combo: HashMap<(usize, usize), Outcome> = HashMap::new();
+        for block in &results {
+            raw.add(&block.raw);
+            sampler.merge(&block.sampler);
+            for (key, outcome) in &block.per_combo {
+                per_combo.entry(*key).or_default().add(outcome);
+            }
+        }
+
+        let matrix = build_cells(spot, |combo, _| per_combo.get(&combo_key(combo)).map(|o| o.normalized()));
+
+        let totals = if villain.is_some() {
+            raw.normalized()
+        } else {
+            // Escalamos a la cantidad de combos del rango para que sea comparable con el modo exhaustivo
+            raw.normalized().scaled(range_weight)
+        };
+        (matrix, totals, Some(sampler.std_error()))
+    }
+}
+
+// --- RANGO VS RANGO ---
+
+// Equity de un rango de hero contra el rango del villano. Los dos lados tienen los mismos totales;
+// `hero` trae celdas/combos del rango del hero (equity de cada combo contra el rango del villano)
+// y `villain` las del villano (equity del rango del hero contra cada combo), como en el modo normal.
+#[derive(Clone, Debug, Default)]
+pub struct RangeEquityReport {
+    pub hero: EquityReport,
+    pub villain: EquityReport,
+}
+
+// Mesas por tarea en rango vs rango (cada mesa evalúa los dos rangos enteros)
+const RVR_CHUNK: usize = 16;
+
+// Combo vivo de un rango: índices de sus cartas y peso
+#[derive(Clone, Copy)]
+struct LiveCombo {
+    cards: (usize, usize),
+    set: CardSet,
+    weight: f64,
+}
+
+// Resultado acumulado por combo de cada lado (hero, villano) y error estándar si se muestreó
+type RangeOutcomes = (Vec<Outcome>, Vec<Outcome>, Option<f64>);
+
+// Resultado parcial de un bloque de mesas
+struct RvrChunk {
+    hero: Vec<Outcome>,
+    villain: Vec<Outcome>,
+    board_equities: Vec<f64>,
+}
+
+impl EquityEngine {
+    // Recorre las mesas (todas postflop; una muestra fija preflop; `iterations` mesas al azar en Monte Carlo)
+    // y en cada una compara los dos rangos enteros ordenándolos por valor de mano.
+    pub fn range_vs_range(&self, hero: &Range, villain: &Range, board: &[MyCard], dead: CardSet, mode: CalcMode, progress: &Progress) -> Option<RangeEquityReport> {
+        let board_set: CardSet = board.iter().copied().collect();
+        let known = dead.union(board_set);
+        let live = |range: &Range| -> Vec<LiveCombo> {
+            range
+                .weighted_combos()
+                .into_iter()
+                .filter(|(c, _)| !known.intersects(CardSet::from(c)))
+                .map(|(c, weight)| LiveCombo { cards: combo_key(&c), set: CardSet::from(&c), weight })
+                .collect()
+        };
+        let (hero_combos, villain_combos) = (live(hero), live(villain));
+
+        let missing = 5 - board.len().min(5);
+        // Preflop sin cartas muertas el resultado exacto sale de la tabla; si no, se recorren las mesas
+        let (hero_raw, villain_raw, std_error) = if mode == CalcMode::Exhaustive && missing == 5 && known.is_empty() {
+            self.preflop_range_vs_range(&hero_combos, &villain_combos, progress)?
+        } else {
+            self.sweep_boards(&hero_combos, &villain_combos, board_set, known, mode, progress)?
+        };
+
+        // Totales: todos los pares (hero, villano, mesa) compatibles, ponderados por los dos rangos
+        let mut joint = Outcome::default();
+        for (combo, raw) in hero_combos.iter().zip(&hero_raw) {
+            joint.add(&raw.scaled(combo.weight));
+        }
+        let hero_weight: f64 = hero_combos.iter().map(|c| c.weight).sum();
+        let totals = joint.normalized().scaled(hero_weight);
+
+        let side = |range: &Range, combos: &[LiveCombo], raw: &[Outcome]| {
+            let outcomes: HashMap<(usize, usize), Outcome> = combos.iter().zip(raw).map(|(c, o)| (c.cards, o.normalized())).collect();
+            let spot = Spot { board: board.to_vec(), dead: known, range: range.clone(), ..Spot::default() };
+            let matrix = build_cells(&spot, |combo, _| outcomes.get(&combo_key(combo)).copied());
+            EquityReport {
+                totals,
+                cells: matrix.cells,
+                combo_outcomes: matrix.combos,
+                range_combos: combos.iter().map(|c| c.weight).sum(),
+                std_error,
+                ..EquityReport::default()
+            }
+        };
+        Some(RangeEquityReport { hero: side(hero, &hero_combos, &hero_raw), villain: side(villain, &villain_combos, &villain_raw) })
+    }
+
+    // Barrido mesa por mesa (todas o una muestra): resultado acumulado de cada combo de los dos lados
+    fn sweep_boards(&self, hero: &[LiveCombo], villain: &[LiveCombo], board_set: CardSet, known: CardSet, mode: CalcMode, progress: &Progress) -> Option<RangeOutcomes> {
+        let missing = 5 - board_set.len().min(5);
+        let mut deck: Vec<MyCard> = known.complement().iter().collect();
+        let mut sample = |count: usize, seed: u64| -> Vec<CardSet> {
+            let mut rng = StdRng::seed_from_u64(seed);
+            (0..count).map(|_| deck.partial_shuffle(&mut rng, missing).0.iter().copied().collect()).collect()
+        };
+        let (completions, sampled) = match mode {
+            CalcMode::MonteCarlo { iterations, seed } if missing > 0 => (sample(iterations as usize, seed), true),
+            CalcMode::Exhaustive if missing > 2 => (sample(PREFLOP_BOARDS, PREFLOP_SEED), true),
+            _ => {
+                let deck: Vec<MyCard> = known.complement().iter().collect();
+                (combinations(&deck, missing).into_iter().map(|extra| extra.into_iter().collect()).collect::<Vec<CardSet>>(), false)
+            }
+        };
+
+        progress.set_total(completions.len());
+        let chunks = parallel::map_chunks(&completions, RVR_CHUNK, self.threads(), |chunk| {
+            let mut result = RvrChunk {
+                hero: vec![Outcome::default(); hero.len()],
+                villain: vec![Outcome::default(); villain.len()],
+                board_equities: Vec::with_capacity(chunk.len()),
+            };
+            for extra in chunk {
+                if progress.is_cancelled() {
+                    break;
+                }
+                progress.advance(1);
+                let full_board = board_set.union(*extra);
+                let hero_ranked = self.rank_live(hero, full_board);
+                let villain_ranked = self.rank_live(villain, full_board);
+                let board_outcome = showdown_sweep(hero, &hero_ranked, villain, &villain_ranked, &mut result.hero, false);
+                showdown_sweep(villain, &villain_ranked, hero, &hero_ranked, &mut result.villain, true);
+                if board_outcome.total() > 0.0 {
+                    result.board_equities.push(board_outcome.equity());
+                }
+            }
+            result
+        });
+        if progress.is_cancelled() {
+            return None;
+        }
+
+        let mut hero_raw = vec![Outcome::default(); hero.len()];
+        let mut villain_raw = vec![Outcome::default(); villain.len()];
+        let mut sampler = Sampler::default();
+        for chunk in &chunks {
+            for (acc, outcome) in hero_raw.iter_mut().zip(&chunk.hero) {
+                acc.add(outcome);
+            }
+            for (acc, outcome) in villain_raw.iter_mut().zip(&chunk.villain) {
+                acc.add(outcome);
+            }
+            for equity in &chunk.board_equities {
+                sampler.push_value(*equity);
+            }
+        }
+
+        Some((hero_raw, villain_raw, sampled.then(|| sampler.std_error())))
+    }
+
+    // Preflop sin cartas muertas: cada par de combos sale de la tabla exacta en vez de recorrer mesas.
+    // Cada par cuenta como una mano, ponderada por el peso del combo del otro lado.
+    fn preflop_range_vs_range(&self, hero: &[LiveCombo], villain: &[LiveCombo], progress: &Progress) -> Option<RangeOutcomes> {
+        let cards = |c: &LiveCombo| [MyCard::from_index(c.cards.0), MyCard::from_index(c.cards.1)];
+        let mut hero_raw = vec![Outcome::default(); hero.len()];
+        let mut villain_raw = vec![Outcome::default(); villain.len()];
+        progress.set_total(hero.len());
+        for (h, h_raw) in hero.iter().zip(hero_raw.iter_mut()) {
+            if progress.is_cancelled() {
+                break;
+            }
+            progress.advance(1);
+            let row = PreflopRow::new(&cards(h));
+            for (v, v_raw) in villain.iter().zip(villain_raw.iter_mut()) {
+                if let Some(outcome) = row.outcome(&cards(v)) {
+                    h_raw.add(&outcome.scaled(v.weight));
+                    v_raw.add(&outcome.scaled(h.weight));
+                }
+            }
+        }
+        if progress.is_cancelled() {
+            return None;
+        }
+        Some((hero_raw, villain_raw, None))
+    }
+
+    // Valor de cada combo vivo en la mesa (None si choca con ella), más los índices ordenados por valor
+    fn rank_live(&self, combos: &[LiveCombo], board: CardSet) -> (Vec<Option<HandRank>>, Vec<usize>) {
+        let ranks: Vec<Option<HandRank>> =
+            combos.iter().map(|c| (!c.set.intersects(board)).then(|| self.rank(board.union(c.set)))).collect();
+        let mut order: Vec<usize> = (0..combos.len()).filter(|&i| ranks[i].is_some()).collect();
+        order.sort_by_key(|&i| ranks[i]);
+        (ranks, order)
+    }
+}
+
+// Compara cada combo de `players` contra todo `opponents` en una mesa, en O(n) sobre las listas ordenadas.
+// Las manos rivales que comparten carta con el combo se descuentan con acumulados por carta.
+// Suma en `acc` el resultado desde el punto de vista del hero (`players_are_villain` invierte victoria/derrota)
+// y devuelve el total de la mesa ponderado por el peso de cada combo de `players`.
+fn showdown_sweep(
+    players: &[LiveCombo],
+    (player_ranks, player_order): &(Vec<Option<HandRank>>, Vec<usize>),
+    opponents: &[LiveCombo],
+    (opponent_ranks, opponent_order): &(Vec<Option<HandRank>>, Vec<usize>),
+    acc: &mut [Outcome],
+    players_are_villain: bool,
+) -> Outcome {
+    // Peso rival total y por carta; y el del combo idéntico (cuenta dos veces al descontar por carta)
+    let mut total = 0.0;
+    let mut by_card = [0.0; 52];
+    let mut same = HashMap::new();
+    for &i in opponent_order {
+        let o = &opponents[i];
+        total += o.weight;
+        by_card[o.cards.0] += o.weight;
+        by_card[o.cards.1] += o.weight;
+        same.insert(o.cards, o.weight);
+    }
+
+    let (mut lt, mut lt_card, mut lt_pos) = (0.0, [0.0; 52], 0);
+    let (mut le, mut le_card, mut le_pos) = (0.0, [0.0; 52], 0);
+    let mut board_total = Outcome::default();
+    for &i in player_order {
+        let p = &players[i];
+        let rank = player_ranks[i];
+        while lt_pos < opponent_order.len() && opponent_ranks[opponent_order[lt_pos]] < rank {
+            let o = &opponents[opponent_order[lt_pos]];
+            lt += o.weight;
+            lt_card[o.cards.0] += o.weight;
+            lt_card[o.cards.1] += o.weight;
+            lt_pos += 1;
+        }
+        while le_pos < opponent_order.len() && opponent_ranks[opponent_order[le_pos]] <= rank {
+            let o = &opponents[opponent_order[le_pos]];
+            le += o.weight;
+            le_card[o.cards.0] += o.weight;
+            le_card[o.cards.1] += o.weight;
+            le_pos += 1;
+        }
+        let (a, b) = p.cards;
+        let same_weight = same.get(&p.cards).copied().unwrap_or(0.0);
+        let below = lt - lt_card[a] - lt_card[b];
+        let below_or_equal = le - le_card[a] - le_card[b] + same_weight;
+        let all = total - by_card[a] - by_card[b] + same_weight;
+
+        let (better, worse) = (below, all - below_or_equal);
+        let ties = below_or_equal - below;
+        let (wins, losses) = if players_are_villain { (worse, better) } else { (better, worse) };
+        let outcome = Outcome { wins, losses, ties, tie_share: ties / 2.0 };
+        acc[i].add(&outcome);
+        board_total.add(&outcome.scaled(p.weight));
+    }
+    board_total
+}
+
+// Recorre las 169 celdas y sus combos. `combo_outcome` recibe el combo y su peso en el rango y devuelve
+// la equity del hero contra él (normalizada a 1.0), o None si no hay datos para él.
+fn build_cells(spot: &Spot, mut combo_outcome: impl FnMut(&[MyCard; 2], f64) -> Option<Outcome>) -> MatrixStats {
+    let mut cells = [[CellStats::default(); 13]; 13];
+    let mut combos = HashMap::new();
+    let mut range_totals = Outcome::default();
+
+    for (r1_idx, row) in cells.iter_mut().enumerate() {
+        for (r2_idx, cell) in row.iter_mut().enumerate() {
+            for combo in cell_combos(r1_idx, r2_idx) {
+                cell.total_combos += 1;
+
+                // Chequear bloqueos (Hero, Villano, Aliados, Board)
+                if spot.is_blocked(&combo) {
+                    cell.blocked += 1;
+                    continue;
+                }
+
+                // Celdas excluidas del rango no cuentan para las estadísticas
+                let weight = spot.range.combo_weight(&combo) as f64;
+                if weight <= 0.0 {
+                    continue;
+                }
+
+                // Si llegamos aquí, el combo es posible para el villano
+                if let Some(outcome) = combo_outcome(&combo, weight) {
+                    cell.record_combo(outcome, weight);
+                    combos.insert(combo_key(&combo), outcome);
+                }
+            }
+            range_totals.add(&cell.outcome);
+        }
+    }
+    MatrixStats { cells, combos, range_totals }
+}
+
+// Llama a `visit` con cada forma de completar `current` con `k` cartas de `deck` (sin guardarlas todas)
+fn for_each_board(deck: &[MyCard], k: usize, current: CardSet, visit: &mut impl FnMut(CardSet)) {
+    if k == 0 {
+        visit(current);
+        return;
+    }
+    for i in 0..deck.len().saturating_sub(k - 1) {
+        let mut next = current;
+        next.insert(deck[i]);
+        for_each_board(&deck[i + 1..], k - 1, next, visit);
+    }
+}
+
+// Todas las combinaciones de `k` cartas (sin orden)
+pub(crate) fn combinations(cards: &[MyCard], k: usize) -> Vec<Vec<MyCard>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut result = Vec::new();
+    for (i, card) in cards.iter().enumerate() {
+        for mut rest in combinations(&cards[i + 1..], k - 1) {
+            rest.insert(0, *card);
+            result.push(rest);
+        }
+    }
+    result
+}
@@ -0,0 +1,235 @@
+use crate::card::{CardSet, MyCard};
+
+// --- EVALUADOR PROPIO (5 a 7 CARTAS) ---
+//
+// Dos tablas que se arman una vez al arrancar:
+// - Color: para cada máscara de 13 bits de un palo con 5+ cartas, el mejor color / escalera de color.
+// - Resto: para cada combinación de cantidades por rango (5 a 7 cartas), su mejor mano sin color.
+//   El índice es un hash perfecto: la posición de ese vector de cantidades entre todos los de la misma
+//   cantidad de cartas, en orden lexicográfico.
+// Con 7 cartas o menos, si hay 5+ de un palo no puede haber full ni póker, así que el color manda.
+
+const NUM_RANKS: usize = 13;
+const MAX_CARDS: usize = 7;
+
+// Categoría de la mano, de peor a mejor
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+}
+
+impl HandCategory {
+    pub const ALL: [HandCategory; 9] = [
+        HandCategory::HighCard, HandCategory::Pair, HandCategory::TwoPair,
+        HandCategory::ThreeOfAKind, HandCategory::Straight, HandCategory::Flush,
+        HandCategory::FullHouse, HandCategory::FourOfAKind, HandCategory::StraightFlush,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            HandCategory::HighCard => "Carta alta",
+            HandCategory::Pair => "Pareja",
+            HandCategory::TwoPair => "Doble pareja",
+            HandCategory::ThreeOfAKind => "Trío",
+            HandCategory::Straight => "Escalera",
+            HandCategory::Flush => "Color",
+            HandCategory::FullHouse => "Full",
+            HandCategory::FourOfAKind => "Póker",
+            HandCategory::StraightFlush => "Escalera de color",
+        }
+    }
+}
+
+// Valor de una mano: mayor = mejor, igual = empate.
+// Empaquetado como categoría << 20 y hasta 5 rangos de desempate de 4 bits (2 = 0 ... A = 12).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct HandRank(u32);
+
+impl HandRank {
+    fn new(category: HandCategory, ranks: &[u8]) -> Self {
+        let mut value = category as u32;
+        for i in 0..5 {
+            value = (value << 4) | ranks.get(i).copied().unwrap_or(0) as u32;
+        }
+        Self(value)
+    }
+
+    pub fn category(self) -> HandCategory {
+        HandCategory::ALL[(self.0 >> 20) as usize]
+    }
+
+    // Rangos de desempate en orden (2 = 0 ... A = 12). Cuántos cuentan depende de la categoría:
+    // ej. pareja = [pareja, 3 kickers], full = [trío, pareja]; el resto queda en 0.
+    pub fn ranks(self) -> [u8; 5] {
+        let mut ranks = [0; 5];
+        for (i, r) in ranks.iter_mut().enumerate() {
+            *r = (self.0 >> (16 - 4 * i) & 0xf) as u8;
+        }
+        ranks
+    }
+}
+
+pub struct HandEvaluator {
+    flush: Vec<HandRank>, // Por máscara de rangos de un palo (2^13)
+    rest: Vec<HandRank>,  // Por hash de las cantidades por rango
+    // contrib[i][resto][c]: cuánto suma al hash poner `c` cartas en el rango `i` quedando `resto` por repartir
+    contrib: [[[u32; 5]; MAX_CARDS + 1]; NUM_RANKS],
+    offsets: [u32; MAX_CARDS + 1], // Comienzo de cada cantidad de cartas en `rest`
+}
+
+impl Default for HandEvaluator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HandEvaluator {
+    pub fn new() -> Self {
+        // vectors[l][k]: vectores de `l` rangos con 0..=4 cartas cada uno que suman `k`
+        let mut vectors = [[0u32; MAX_CARDS + 1]; NUM_RANKS + 1];
+        vectors[0][0] = 1;
+        for l in 1..=NUM_RANKS {
+            for k in 0..=MAX_CARDS {
+                vectors[l][k] = (0..=k.min(4)).map(|v| vectors[l - 1][k - v]).sum();
+            }
+        }
+
+        let mut contrib = [[[0u32; 5]; MAX_CARDS + 1]; NUM_RANKS];
+        for (i, by_rest) in contrib.iter_mut().enumerate() {
+            for (rest, by_count) in by_rest.iter_mut().enumerate() {
+                for c in 1..5 {
+                    let v = c - 1;
+                    by_count[c] = by_count[c - 1] + if v <= rest { vectors[NUM_RANKS - i - 1][rest - v] } else { 0 };
+                }
+            }
+        }
+
+        let mut offsets = [0u32; MAX_CARDS + 1];
+        for k in 1..=MAX_CARDS {
+            offsets[k] = offsets[k - 1] + vectors[NUM_RANKS][k - 1];
+        }
+        let size = (offsets[MAX_CARDS] + vectors[NUM_RANKS][MAX_CARDS]) as usize;
+
+        let mut evaluator = Self { flush: vec![HandRank::default(); 1 << NUM_RANKS], rest: vec![HandRank::default(); size], contrib, offsets };
+
+        for mask in 0u16..(1 << NUM_RANKS) {
+            if mask.count_ones() >= 5 {
+                evaluator.flush[mask as usize] = flush_rank(mask);
+            }
+        }
+        let mut counts = [0u8; NUM_RANKS];
+        for total in 5..=MAX_CARDS {
+            evaluator.fill_rest(&mut counts, 0, total);
+        }
+        evaluator
+    }
+
+    // Recorre todos los vectores de cantidades que suman `left` desde el rango `i`
+    fn fill_rest(&mut self, counts: &mut [u8; NUM_RANKS], i: usize, left: usize) {
+        if i == NUM_RANKS {
+            if left == 0 {
+                let index = self.hash(counts);
+                self.rest[index] = rest_rank(counts);
+            }
+            return;
+        }
+        for c in 0..=left.min(4) {
+            counts[i] = c as u8;
+            self.fill_rest(counts, i + 1, left - c);
+        }
+        counts[i] = 0;
+    }
+
+    fn hash(&self, counts: &[u8; NUM_RANKS]) -> usize {
+        let total: usize = counts.iter().map(|&c| c as usize).sum();
+        let mut index = self.offsets[total];
+        let mut rest = total;
+        for (i, &c) in counts.iter().enumerate() {
+            index += self.contrib[i][rest][c as usize];
+            rest -= c as usize;
+        }
+        index as usize
+    }
+
+    // Mejor mano de 5 a 7 cartas
+    pub fn evaluate(&self, cards: CardSet) -> HandRank {
+        debug_assert!((5..=MAX_CARDS).contains(&cards.len()), "se evalúan de 5 a 7 cartas");
+        let mut counts = [0u8; NUM_RANKS];
+        let mut suits = [0u16; 4];
+        for index in cards.indices() {
+            // MyCard::index ordena los rangos de As a Dos; acá 0 = Dos
+            let rank = NUM_RANKS - 1 - index / 4;
+            counts[rank] += 1;
+            suits[index % 4] |= 1 << rank;
+        }
+        if let Some(&mask) = suits.iter().find(|m| m.count_ones() >= 5) {
+            return self.flush[mask as usize];
+        }
+        self.rest[self.hash(&counts)]
+    }
+
+    pub fn evaluate_cards(&self, cards: &[MyCard]) -> HandRank {
+        self.evaluate(cards.iter().copied().collect())
+    }
+}
+
+// Rango más alto de una escalera dentro de la máscara (la rueda A-5 cuenta con el 5 arriba)
+pub(crate) fn straight_high(mask: u16) -> Option<u8> {
+    for high in (4..NUM_RANKS).rev() {
+        let needed = 0b11111 << (high - 4);
+        if mask & needed == needed {
+            return Some(high as u8);
+        }
+    }
+    let wheel = 0b1_0000_0000_1111;
+    (mask & wheel == wheel).then_some(3)
+}
+
+// Rangos presentes en la máscara, de mayor a menor
+fn ranks_desc(mask: u16) -> impl Iterator<Item = u8> {
+    (0..NUM_RANKS as u8).rev().filter(move |r| mask & (1 << r) != 0)
+}
+
+fn flush_rank(mask: u16) -> HandRank {
+    match straight_high(mask) {
+        Some(high) => HandRank::new(HandCategory::StraightFlush, &[high]),
+        None => HandRank::new(HandCategory::Flush, &ranks_desc(mask).take(5).collect::<Vec<_>>()),
+    }
+}
+
+// Mejor mano sin color a partir de cuántas cartas hay de cada rango
+fn rest_rank(counts: &[u8; NUM_RANKS]) -> HandRank {
+    let with = |n: u8| ranks_desc(0x1fff).filter(move |&r| counts[r as usize] >= n);
+    let mask = (0..NUM_RANKS).filter(|&r| counts[r] > 0).fold(0u16, |m, r| m | 1 << r);
+    let kickers = |used: &[u8], n: usize| ranks_desc(mask).filter(|r| !used.contains(r)).take(n).collect::<Vec<_>>();
+
+    if let Some(quads) = with(4).next() {
+        return HandRank::new(HandCategory::FourOfAKind, &[&[quads][..], &kickers(&[quads], 1)].concat());
+    }
+    let trips: Vec<u8> = with(3).collect();
+    if let Some(&top) = trips.first() {
+        if let Some(pair) = with(2).find(|&r| r != top) {
+            return HandRank::new(HandCategory::FullHouse, &[top, pair]);
+        }
+    }
+    if let Some(high) = straight_high(mask) {
+        return HandRank::new(HandCategory::Straight, &[high]);
+    }
+    if let Some(&top) = trips.first() {
+        return HandRank::new(HandCategory::ThreeOfAKind, &[&[top][..], &kickers(&[top], 2)].concat());
+    }
+    let pairs: Vec<u8> = with(2).collect();
+    match pairs.as_slice() {
+        [high, low, ..] => HandRank::new(HandCategory::TwoPair, &[&[*high, *low][..], &kickers(&[*high, *low], 1)].concat()),
+        [pair] => HandRank::new(HandCategory::Pair, &[&[*pair][..], &kickers(&[*pair], 3)].concat()),
+        [] => HandRank::new(HandCategory::HighCard, &kickers(&[], 5)),
+    }
+}
@@ -0,0 +1,68 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::{combinations, EquityEngine, Outcome, Progress, Spot};
+use crate::parallel;
+
+// --- EQUITY POR CARTA SIGUIENTE ---
+//
+// En el flop, la equity del hero contra el villano (o su rango) después de cada turn posible;
+// en el turn, después de cada river. Sirve para ver de un vistazo las cartas que asustan y las buenas.
+
+#[derive(Clone, Debug, Default)]
+pub struct NextCardReport {
+    pub cards: Vec<(MyCard, Outcome)>, // Cartas que pueden salir, con el resultado normalizado (una mano rival = 1.0)
+}
+
+impl NextCardReport {
+    pub fn outcome(&self, card: MyCard) -> Option<Outcome> {
+        self.cards.iter().find(|(c, _)| *c == card).map(|(_, o)| *o)
+    }
+}
+
+impl EquityEngine {
+    // Sólo flop y turn. Los rivales extra (multiway) no cuentan. None si se canceló o no aplica.
+    pub fn next_card_equities(&self, spot: &Spot, progress: &Progress) -> Option<NextCardReport> {
+        let hero: [MyCard; 2] = spot.hero.as_slice().try_into().ok()?;
+        if !(3..=4).contains(&spot.board.len()) {
+            return None;
+        }
+        let board: CardSet = spot.board.iter().copied().collect();
+        let villains = match spot.villain.as_slice() {
+            [v1, v2] => vec![([*v1, *v2], 1.0)],
+            _ => spot.live_range_combos(),
+        };
+        let unseen: Vec<MyCard> = spot.dead.complement().iter().collect();
+        let missing = 4 - spot.board.len(); // Cartas que faltan después de la siguiente
+
+        progress.set_total(unseen.len());
+        let outcomes = parallel::map_indexed(unseen.len(), self.threads(), |i| {
+            let mut outcome = Outcome::default();
+            if progress.is_cancelled() {
+                return outcome;
+            }
+            let card = unseen[i];
+            let mut next = board;
+            next.insert(card);
+            let live: Vec<&([MyCard; 2], f64)> = villains.iter().filter(|(c, _)| !CardSet::from(c).contains(card)).collect();
+            let rest: Vec<MyCard> = unseen.iter().copied().filter(|c| *c != card).collect();
+            for extra in combinations(&rest, missing) {
+                let full_board = extra.into_iter().fold(next, |mut set, c| {
+                    set.insert(c);
+                    set
+                });
+                let hero_rank = self.rank(full_board.union(CardSet::from(&hero)));
+                for (combo, weight) in &live {
+                    let cards = CardSet::from(combo);
+                    if !cards.intersects(full_board) {
+                        outcome.record_weighted(hero_rank, self.rank(full_board.union(cards)), *weight);
+                    }
+                }
+            }
+            progress.advance(1);
+            outcome.normalized()
+        });
+        if progress.is_cancelled() {
+            return None;
+        }
+        Some(NextCardReport { cards: unseen.into_iter().zip(outcomes).collect() })
+    }
+}
@@ -0,0 +1,116 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::Spot;
+use std::cmp::Ordering;
+
+// --- ISOMORFISMO DE PALOS ---
+//
+// Los palos no tienen valor: As 8d Qc y Ah 8s Qd son la misma situación con los palos renombrados.
+// Llevamos cada Spot a una forma canónica (la menor entre las 24 permutaciones) para que situaciones
+// equivalentes compartan cálculo y caché, y usamos las permutaciones que dejan el Spot igual
+// (su estabilizador) para no repetir combos equivalentes del villano.
+
+// Permutación de palos: el palo SUITS[i] pasa a ser SUITS[perm[i]]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SuitPermutation([usize; 4]);
+
+impl Default for SuitPermutation {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl SuitPermutation {
+    pub fn identity() -> Self {
+        Self([0, 1, 2, 3])
+    }
+
+    // Las 24 permutaciones (la identidad primero)
+    pub fn all() -> Vec<SuitPermutation> {
+        let mut perms = Vec::with_capacity(24);
+        for a in 0..4 {
+            for b in (0..4).filter(|&b| b != a) {
+                for c in (0..4).filter(|&c| c != a && c != b) {
+                    perms.push(Self([a, b, c, 6 - a - b - c]));
+                }
+            }
+        }
+        perms
+    }
+
+    pub fn is_identity(self) -> bool {
+        self == Self::identity()
+    }
+
+    pub fn inverse(self) -> Self {
+        let mut inv = [0; 4];
+        for (from, to) in self.0.into_iter().enumerate() {
+            inv[to] = from;
+        }
+        Self(inv)
+    }
+
+    // Índice de carta (MyCard::index) con el palo cambiado
+    pub fn apply_index(self, index: usize) -> usize {
+        index / 4 * 4 + self.0[index % 4]
+    }
+
+    pub fn apply(self, card: MyCard) -> MyCard {
+        MyCard::from_index(self.apply_index(card.index()))
+    }
+
+    pub fn apply_combo(self, combo: &[MyCard; 2]) -> [MyCard; 2] {
+        [self.apply(combo[0]), self.apply(combo[1])]
+    }
+
+    pub fn apply_set(self, set: CardSet) -> CardSet {
+        set.iter().map(|c| self.apply(c)).collect()
+    }
+
+    // Spot con los palos cambiados. Las cartas quedan ordenadas por índice (el orden no cambia el cálculo).
+    pub fn apply_spot(self, spot: &Spot) -> Spot {
+        let cards = |cards: &[MyCard]| {
+            let mut mapped: Vec<MyCard> = cards.iter().map(|c| self.apply(*c)).collect();
+            mapped.sort_by_key(|c| c.index());
+            mapped
+        };
+        Spot {
+            hero: cards(&spot.hero),
+            villain: cards(&spot.villain),
+            board: cards(&spot.board),
+            dead: self.apply_set(spot.dead),
+            range: spot.range.permuted_suits(self),
+            others: spot.others.iter().map(|range| range.permuted_suits(self)).collect(),
+        }
+    }
+}
+
+// Forma canónica del Spot y la permutación que lleva del original a ella.
+// Dos Spots isomorfos dan exactamente el mismo Spot canónico.
+pub fn canonical(spot: &Spot) -> (Spot, SuitPermutation) {
+    SuitPermutation::all()
+        .into_iter()
+        .map(|perm| (perm.apply_spot(spot), perm))
+        .min_by(|(a, _), (b, _)| compare_spots(a, b))
+        .expect("hay 24 permutaciones")
+}
+
+// Permutaciones que dejan el Spot igual (siempre incluye la identidad)
+pub fn stabilizer(spot: &Spot) -> Vec<SuitPermutation> {
+    let sorted = SuitPermutation::identity().apply_spot(spot);
+    SuitPermutation::all().into_iter().filter(|perm| perm.apply_spot(spot) == sorted).collect()
+}
+
+// Orden total entre Spots: mesa, hero, villano, cartas muertas y por último los rangos
+fn compare_spots(a: &Spot, b: &Spot) -> Ordering {
+    let indices = |cards: &[MyCard]| cards.iter().map(|c| c.index()).collect::<Vec<_>>();
+    indices(&a.board)
+        .cmp(&indices(&b.board))
+        .then_with(|| indices(&a.hero).cmp(&indices(&b.hero)))
+        .then_with(|| indices(&a.villain).cmp(&indices(&b.villain)))
+        .then_with(|| a.dead.indices().cmp(b.dead.indices()))
+        .then_with(|| a.range.compare_weights(&b.range))
+        .then_with(|| {
+            let ranges = a.others.iter().zip(&b.others).map(|(x, y)| x.compare_weights(y));
+            ranges.fold(a.others.len().cmp(&b.others.len()), Ordering::then)
+        })
+}
@@ -0,0 +1,34 @@
+// Motor de cálculo del Poker Solver.
+// La GUI (main.rs) y las herramientas (debug_hand.rs) consumen este mismo API.
+
+pub mod card;
+pub mod describe;
+pub mod draws;
+pub mod duel;
+pub mod equity;
+pub mod evaluator;
+pub mod heatmap;
+pub mod iso;
+pub mod made_hand;
+pub mod outs;
+mod parallel;
+pub mod preflop;
+pub mod range;
+pub mod showdown;
+pub mod strength;
+pub mod worker;
+
+pub use card::{cell_combos, cell_name, combo_cell, combo_key, rank_to_str, CardSet, MyCard, Rank, Suit, RANKS, SUITS};
+pub use describe::{describe, Language};
+pub use draws::{DrawKind, Draws};
+pub use duel::DuelReport;
+pub use equity::{CalcMode, CellStats, EquityEngine, EquityReport, Outcome, Progress, RangeEquityReport, Spot, MULTIWAY_ITERATIONS, PREFLOP_BOARDS};
+pub use evaluator::{HandCategory, HandEvaluator, HandRank};
+pub use heatmap::NextCardReport;
+pub use iso::SuitPermutation;
+pub use made_hand::{MadeHand, RangeBreakdown};
+pub use outs::{Out, OutsReport};
+pub use range::Range;
+pub use showdown::{SeatHand, SeatResult, ShowdownReport};
+pub use strength::{BetterHand, HandStrength};
+pub use worker::{EquityWorker, NextCardWorker, RangeWorker, ShowdownWorker};
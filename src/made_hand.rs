@@ -0,0 +1,133 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::EquityEngine;
+use crate::evaluator::HandCategory;
+use crate::range::Range;
+
+// --- MANOS HECHAS (ESTILO FLOPZILLA) ---
+
+// Categoría de la mano hecha con la mesa actual, con las parejas separadas según la mesa
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MadeHand {
+    HighCard, // Incluye lo que está entero en la mesa (pareja, doble pareja, trío...)
+    Underpair,
+    BottomPair,
+    MiddlePair,
+    TopPair,
+    Overpair,
+    TwoPair,
+    Trips, // Una carta de la mano + pareja en la mesa
+    Set,   // Pareja en mano + una carta de la mesa
+    Straight,
+    Flush,
+    FullHouse,
+    Quads,
+    StraightFlush,
+}
+
+impl MadeHand {
+    // De mejor a peor (orden de la tabla)
+    pub const ALL: [MadeHand; 14] = [
+        MadeHand::StraightFlush, MadeHand::Quads, MadeHand::FullHouse, MadeHand::Flush,
+        MadeHand::Straight, MadeHand::Set, MadeHand::Trips, MadeHand::TwoPair,
+        MadeHand::Overpair, MadeHand::TopPair, MadeHand::MiddlePair, MadeHand::BottomPair,
+        MadeHand::Underpair, MadeHand::HighCard,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            MadeHand::HighCard => "Carta alta",
+            MadeHand::Underpair => "Pareja por debajo de la mesa",
+            MadeHand::BottomPair => "Pareja baja",
+            MadeHand::MiddlePair => "Pareja media",
+            MadeHand::TopPair => "Top pair",
+            MadeHand::Overpair => "Overpair",
+            MadeHand::TwoPair => "Doble pareja",
+            MadeHand::Trips => "Trío (trips)",
+            MadeHand::Set => "Set",
+            MadeHand::Straight => "Escalera",
+            MadeHand::Flush => "Color",
+            MadeHand::FullHouse => "Full",
+            MadeHand::Quads => "Póker",
+            MadeHand::StraightFlush => "Escalera de color",
+        }
+    }
+}
+
+// Peso del rango en cada categoría (combos vivos ponderados)
+#[derive(Clone, Debug, Default)]
+pub struct RangeBreakdown {
+    pub rows: Vec<(MadeHand, f64)>, // En el orden de MadeHand::ALL, sólo las que tienen peso
+    pub total: f64,
+}
+
+impl EquityEngine {
+    // Mano hecha de `hole` con la mesa (3 a 5 cartas). Sólo cuenta lo que usa alguna carta de la
+    // mano: la pareja, doble pareja, trío, full o póker que está entero en la mesa es carta alta
+    // (o la pareja que la mano forme aparte).
+    pub fn made_hand(&self, hole: &[MyCard; 2], board: &[MyCard]) -> MadeHand {
+        let board_set = board.iter().copied().collect::<CardSet>();
+        let hand = self.rank(board_set.union(CardSet::from(hole)));
+        // River: si la mesa sola ya hace la misma jugada, la mano no aporta nada
+        if board.len() == 5 && self.rank(board_set) == hand {
+            return MadeHand::HighCard;
+        }
+        let rank = |c: &MyCard| 12 - c.index() / 4; // 0 = Dos
+        let board_ranks: Vec<usize> = board.iter().map(rank).collect();
+        let (top, bottom) = (board_ranks.iter().max().copied().unwrap_or(0), board_ranks.iter().min().copied().unwrap_or(0));
+        let pocket_pair = rank(&hole[0]) == rank(&hole[1]);
+        let uses = |r: u8| hole.iter().any(|c| rank(c) == r as usize);
+
+        // Pareja de rango `r` formada con la mano
+        let pair = |r: usize| match r {
+            r if pocket_pair && r > top => MadeHand::Overpair,
+            r if pocket_pair && r < bottom => MadeHand::Underpair,
+            _ if pocket_pair => MadeHand::MiddlePair,
+            r if r == top => MadeHand::TopPair,
+            r if r == bottom => MadeHand::BottomPair,
+            _ => MadeHand::MiddlePair,
+        };
+
+        let [first, second, ..] = hand.ranks();
+        match hand.category() {
+            HandCategory::HighCard => MadeHand::HighCard,
+            HandCategory::Pair if uses(first) => pair(first as usize),
+            HandCategory::Pair => MadeHand::HighCard,
+            // Con una pareja de la mesa, la doble pareja es en realidad la pareja de la mano
+            HandCategory::TwoPair => match (uses(first), uses(second)) {
+                (true, true) => MadeHand::TwoPair,
+                (true, false) => pair(first as usize),
+                (false, true) => pair(second as usize),
+                (false, false) => MadeHand::HighCard,
+            },
+            HandCategory::ThreeOfAKind if !uses(first) => MadeHand::HighCard,
+            HandCategory::ThreeOfAKind if pocket_pair => MadeHand::Set,
+            HandCategory::ThreeOfAKind => MadeHand::Trips,
+            HandCategory::Straight => MadeHand::Straight,
+            HandCategory::Flush => MadeHand::Flush,
+            HandCategory::FullHouse if !uses(first) && !uses(second) => MadeHand::HighCard,
+            HandCategory::FullHouse => MadeHand::FullHouse,
+            HandCategory::FourOfAKind if !uses(first) => MadeHand::HighCard,
+            HandCategory::FourOfAKind => MadeHand::Quads,
+            HandCategory::StraightFlush => MadeHand::StraightFlush,
+        }
+    }
+
+    // Reparto del rango por mano hecha. `dead` = cartas conocidas (incluida la mesa).
+    pub fn range_breakdown(&self, range: &Range, board: &[MyCard], dead: CardSet) -> RangeBreakdown {
+        let mut weights = [0.0; MadeHand::ALL.len()];
+        let mut total = 0.0;
+        if board.len() >= 3 {
+            for (combo, weight) in range.weighted_combos() {
+                if dead.intersects(CardSet::from(&combo)) {
+                    continue;
+                }
+                let made = self.made_hand(&combo, board);
+                let row = MadeHand::ALL.iter().position(|m| *m == made).expect("todas las categorías");
+                weights[row] += weight;
+                total += weight;
+            }
+        }
+        let rows = MadeHand::ALL.into_iter().zip(weights).filter(|(_, w)| *w > 0.0).collect();
+        RangeBreakdown { rows, total }
+    }
+}
@@ -1,62 +1,107 @@
 use eframe::egui;
-use poker::{cards, Card, Evaluator, Rank, Suit};
-use std::collections::HashSet;
-use std::str::FromStr;
+use poker_solver::{cell_combos, cell_name, combo_cell, describe, Language, DrawKind, Draws, DuelReport, HandStrength, RangeBreakdown, combo_key, rank_to_str, CalcMode, EquityEngine, EquityReport, EquityWorker, NextCardWorker, Outcome, OutsReport, CardSet, MyCard, Range, RangeEquityReport, RangeWorker, SeatHand, ShowdownWorker, Spot, Suit, MULTIWAY_ITERATIONS, PREFLOP_BOARDS, RANKS};
+use std::sync::Arc;
 
 // --- ESTRUCTURAS DE DATOS ---
 
-#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
-struct MyCard {
-    rank: Rank,
-    suit: Suit,
+fn card_color(card: &MyCard) -> egui::Color32 {
+    match card.suit {
+        Suit::Hearts => egui::Color32::from_rgb(235, 60, 60),   // Rojo
+        Suit::Diamonds => egui::Color32::from_rgb(60, 100, 235), // Azul
+        Suit::Clubs => egui::Color32::from_rgb(60, 200, 60),    // Verde
+        Suit::Spades => egui::Color32::from_rgb(180, 180, 180), // Gris Claro
+    }
 }
 
-impl MyCard {
-    fn to_poker_card(&self) -> Card {
-        // La crate 'poker' permite crear cartas directamente con Rank y Suit
-        Card::new(self.rank, self.suit)
+// Escala continua de equity: rojo (0%) -> ámbar (50%) -> verde (100%)
+fn equity_color(equity: f64) -> egui::Color32 {
+    let red = egui::Color32::from_rgb(200, 50, 50);
+    let amber = egui::Color32::from_rgb(210, 160, 40);
+    let green = egui::Color32::from_rgb(50, 180, 50);
+    let e = equity.clamp(0.0, 1.0) as f32;
+    if e < 0.5 {
+        lerp_color(red, amber, e * 2.0)
+    } else {
+        lerp_color(amber, green, (e - 0.5) * 2.0)
     }
+}
 
-    fn rank_char(&self) -> char {
-        match self.rank {
-            Rank::Two => '2', Rank::Three => '3', Rank::Four => '4', Rank::Five => '5',
-            Rank::Six => '6', Rank::Seven => '7', Rank::Eight => '8', Rank::Nine => '9',
-            Rank::Ten => 'T', Rank::Jack => 'J', Rank::Queen => 'Q', Rank::King => 'K',
-            Rank::Ace => 'A',
-        }
-    }
+fn lerp_color(a: egui::Color32, b: egui::Color32, t: f32) -> egui::Color32 {
+    let mix = |x: u8, y: u8| (x as f32 + (y as f32 - x as f32) * t).round() as u8;
+    egui::Color32::from_rgb(mix(a.r(), b.r()), mix(a.g(), b.g()), mix(a.b(), b.b()))
+}
+
+// Celda de la matriz: el color ocupa sólo la fracción `weight` de la altura (peso en el rango)
+fn matrix_cell(ui: &mut egui::Ui, name: &str, fill: egui::Color32, text_color: egui::Color32, weight: f32) -> egui::Response {
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(35.0, 35.0), egui::Sense::click());
+    if ui.is_rect_visible(rect) {
+        let painter = ui.painter();
+        painter.rect_filled(rect, 3.0, egui::Color32::from_rgb(20, 20, 20));
 
-    fn suit_char(&self) -> char {
-        match self.suit {
-            Suit::Clubs => 'c',
-            Suit::Diamonds => 'd',
-            Suit::Hearts => 'h',
-            Suit::Spades => 's',
+        let fill_rect = egui::Rect::from_min_max(egui::pos2(rect.min.x, rect.max.y - rect.height() * weight), rect.max);
+        painter.rect_filled(fill_rect, 3.0, fill);
+
+        if response.hovered() {
+            painter.rect_stroke(rect, 3.0, egui::Stroke::new(1.0, egui::Color32::WHITE));
+        }
+        painter.text(rect.center(), egui::Align2::CENTER_CENTER, name, egui::FontId::proportional(12.0), text_color);
+        if weight > 0.0 && weight < 1.0 {
+            painter.text(rect.right_bottom() - egui::vec2(2.0, 1.0), egui::Align2::RIGHT_BOTTOM, format!("{:.0}%", weight * 100.0), egui::FontId::proportional(8.0), text_color);
         }
     }
+    response
+}
 
-    fn display_text(&self) -> String {
-        let r = match self.rank {
-            Rank::Ten => "10".to_string(),
-            _ => self.rank_char().to_string(),
-        };
-        let s = match self.suit {
-            Suit::Clubs => "♣",
-            Suit::Diamonds => "♦",
-            Suit::Hearts => "♥",
-            Suit::Spades => "♠",
-        };
-        format!("{}{}", r, s)
-    }
+// Cartas siguientes agrupadas según el resultado del hero: ganas siempre, pierdes siempre, empate o mixto.
+// Devuelve la carta clickeada (si `clickable`).
+fn render_next_cards(ui: &mut egui::Ui, cards: &[(MyCard, Outcome)], clickable: bool) -> Option<MyCard> {
+    let groups = [
+        ("Ganas:", egui::Color32::from_rgb(50, 180, 50)),
+        ("Pierdes:", egui::Color32::from_rgb(200, 50, 50)),
+        ("Empate:", egui::Color32::from_rgb(100, 100, 255)),
+        ("Mixto:", egui::Color32::from_rgb(210, 160, 40)),
+    ];
+    let group_of = |o: &Outcome| {
+        if o.wins == o.total() { 0 } else if o.losses == o.total() { 1 } else if o.ties == o.total() { 2 } else { 3 }
+    };
+    let mut clicked = None;
+    egui::Grid::new(("next_cards", clickable)).spacing([10.0, 4.0]).show(ui, |ui| {
+        for (g, (label, color)) in groups.into_iter().enumerate() {
+            let group: Vec<&(MyCard, Outcome)> = cards.iter().filter(|(_, o)| group_of(o) == g).collect();
+            if group.is_empty() {
+                continue;
+            }
+            ui.label(egui::RichText::new(format!("{} {}", label, group.len())).color(color).strong());
+            ui.horizontal_wrapped(|ui| {
+                for (card, o) in group {
+                    let mut text = card.display_text();
+                    if g == 3 {
+                        text = format!("{} {:.0}%", text, o.equity() * 100.0);
+                    }
+                    let button = egui::Button::new(egui::RichText::new(text).color(card_color(card)).strong()).fill(egui::Color32::from_gray(30)).stroke(egui::Stroke::new(1.0, color));
+                    let response = ui.add(button).on_hover_text(format!("Ganas {:.0} · Pierdes {:.0} · Empate {:.0}", o.wins, o.losses, o.ties));
+                    if clickable && response.clicked() {
+                        clicked = Some(*card);
+                    }
+                }
+            });
+            ui.end_row();
+        }
+    });
+    clicked
+}
 
-    fn color(&self) -> egui::Color32 {
-        match self.suit {
-            Suit::Hearts => egui::Color32::from_rgb(235, 60, 60),   // Rojo
-            Suit::Diamonds => egui::Color32::from_rgb(60, 100, 235), // Azul
-            Suit::Clubs => egui::Color32::from_rgb(60, 200, 60),    // Verde
-            Suit::Spades => egui::Color32::from_rgb(180, 180, 180), // Gris Claro
+// Rejilla 13x4 de la baraja: una fila por palo, columnas de As a Dos (selector de cartas y mapa de calor)
+fn card_grid(ui: &mut egui::Ui, id: &str, mut cell: impl FnMut(&mut egui::Ui, MyCard)) {
+    let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+    egui::Grid::new(id).spacing([5.0, 5.0]).show(ui, |ui| {
+        for suit in suits {
+            for rank in RANKS {
+                cell(ui, MyCard { rank, suit });
+            }
+            ui.end_row();
         }
-    }
+    });
 }
 
 // --- APP STATE ---
@@ -71,51 +116,169 @@ struct PokerApp {
     // Estado de selección
     selected_card_idx: Option<CardSlot>, // Qué slot estamos editando
 
+    // Hero como rango (rango vs rango): se edita en la misma matriz que el del villano
+    hero_is_range: bool,
+    hero_range: Range,
+    edit_hero_range: bool, // true = la matriz muestra/edita el rango del hero
+
+    // Duelo 1 vs 1: turn elegido para ver el resultado de cada river
+    duel_turn: Option<MyCard>,
+    // Duelo de la mesa actual y del turn elegido (se recalculan sólo cuando cambian las cartas)
+    duel_cache: Option<(DuelKey, DuelReport)>,
+    river_cache: Option<(DuelKey, DuelReport)>,
+    // Outs del último Spot (se recalculan sólo cuando cambia)
+    outs_cache: Option<(Spot, Option<OutsReport>)>,
+    // Fuerza relativa del hero para (mano, mesa, cartas conocidas)
+    strength_cache: Option<(StrengthKey, Option<HandStrength>)>,
+    // Reparto del rango de la matriz por mano hecha (rango, mesa, cartas conocidas)
+    breakdown_cache: Option<(RangeKey, RangeBreakdown)>,
+    // Proyectos de todos los combos con la mesa actual (panel de proyectos y filtro de la matriz)
+    board_draws: Option<BoardDraws>,
+
     // Matriz
-    excluded_cells: HashSet<(usize, usize)>, // Celdas desactivadas por el usuario (click)
+    villain_range: Range, // Rango del villano: peso por celda (0% = desactivada por el usuario)
+    brush_weight: f32,    // Peso que se aplica al hacer click en una celda
+    range_text: String,   // Rango en notación de texto (ej: "22+, A2s+, KTo+")
+    range_error: Option<String>,
+    editing_cell: Option<(usize, usize)>, // Celda abierta en el editor de combos (click derecho)
+    gradient_colors: bool, // true = color por equity de la celda; false = vista por umbral (rojo si algún combo te gana)
+    draw_filter: Option<DrawKind>, // Resalta sólo las celdas con algún combo con ese proyecto
+    language: Language,            // Idioma de las descripciones de manos
+
+    // Multiway: rivales en la mano (1-9). El 1º es el villano/matriz; los demás usan mano aleatoria o la matriz.
+    opponents: usize,
+    others_use_matrix: [bool; 8],
     
-    // Evaluador
-    evaluator: Evaluator,
+    // Modo de cálculo
+    use_monte_carlo: bool,
+    mc_iterations: u32,
+    mc_seed: u64,
+    threads: usize, // Hilos de cálculo (no cambia el resultado, sólo la velocidad)
+
+    // Showdown de varios jugadores con las manos a la vista (estilo PokerStove)
+    showdown_mode: bool,
+    seat_count: usize,
+    seats: Vec<SeatInput>,
+
+    // Motor de equity (lib)
+    engine: Arc<EquityEngine>,
+    // Cálculo en segundo plano: sólo se relanza cuando cambian las cartas, el rango o el modo
+    worker: EquityWorker,
+    showdown_worker: ShowdownWorker,
+    range_worker: RangeWorker,
+    next_card_worker: NextCardWorker,
 }
 
+// Cartas de un duelo: hero, villano, mesa y aliados (muertas)
+type DuelKey = ([MyCard; 2], [MyCard; 2], Vec<MyCard>, CardSet);
+// Mano del hero, mesa y cartas conocidas
+type StrengthKey = ([MyCard; 2], Vec<MyCard>, CardSet);
+// Rango, mesa y cartas conocidas
+type RangeKey = (Range, Vec<MyCard>, CardSet);
+
 #[derive(Clone, Copy, PartialEq, Debug)]
 enum CardSlot {
     Hero(usize),
     Villain(usize), // Nuevo
     Friend(usize, usize), // friend_idx, card_idx
     Board(usize),
+    Seat(usize, usize), // Showdown: jugador, carta
+}
+
+// Un jugador del modo showdown: 2 cartas a la vista o un rango en texto
+struct SeatInput {
+    cards: [Option<MyCard>; 2],
+    use_range: bool,
+    range_text: String,
+    range: Range,
+    error: Option<String>,
+}
+
+impl Default for SeatInput {
+    fn default() -> Self {
+        Self { cards: [None; 2], use_range: false, range_text: String::new(), range: Range::full(), error: None }
+    }
+}
+
+// Jugadores que se pueden cargar en la pestaña de showdown (el motor admite hasta showdown::MAX_SEATS)
+const GUI_SEATS: usize = 6;
+
+// Proyectos de cada combo (por combo_key) con una mesa: sólo dependen de la mesa,
+// así que se clasifican una vez cuando cambia y el resto son búsquedas
+struct BoardDraws {
+    board: Vec<MyCard>,
+    draws: Vec<Draws>, // 52 * 52, índice = menor * 52 + mayor
+}
+
+impl BoardDraws {
+    fn new(board: &[MyCard]) -> Self {
+        let mut draws = vec![Draws::default(); 52 * 52];
+        for a in 0..52 {
+            for b in a + 1..52 {
+                draws[a * 52 + b] = Draws::classify(&[MyCard::from_index(a), MyCard::from_index(b)], board);
+            }
+        }
+        Self { board: board.to_vec(), draws }
+    }
+
+    fn get(&self, combo: &[MyCard; 2]) -> Draws {
+        let (a, b) = combo_key(combo);
+        self.draws[a * 52 + b]
+    }
 }
 
 impl Default for PokerApp {
     fn default() -> Self {
+        let engine = Arc::new(EquityEngine::new());
         Self {
             hero_hand: [None; 2],
             villain_hand: [None; 2],
             friends_hands: [[None; 2]; 3],
             board: vec![None; 5],
             selected_card_idx: None,
-            excluded_cells: HashSet::new(),
-            evaluator: Evaluator::new(),
+            hero_is_range: false,
+            hero_range: Range::empty(),
+            edit_hero_range: false,
+            duel_turn: None,
+            duel_cache: None,
+            river_cache: None,
+            outs_cache: None,
+            strength_cache: None,
+            breakdown_cache: None,
+            board_draws: None,
+            villain_range: Range::full(),
+            brush_weight: 1.0,
+            range_text: String::new(),
+            range_error: None,
+            editing_cell: None,
+            gradient_colors: true,
+            draw_filter: None,
+            language: Language::Spanish,
+            opponents: 1,
+            others_use_matrix: [false; 8],
+            use_monte_carlo: false,
+            mc_iterations: 10_000,
+            mc_seed: 42,
+            threads: engine.threads(),
+            showdown_mode: false,
+            seat_count: 3,
+            seats: (0..GUI_SEATS).map(|_| SeatInput::default()).collect(),
+            worker: EquityWorker::new(Arc::clone(&engine)),
+            showdown_worker: ShowdownWorker::new(Arc::clone(&engine)),
+            range_worker: RangeWorker::new(Arc::clone(&engine)),
+            next_card_worker: NextCardWorker::new(Arc::clone(&engine)),
+            engine,
         }
     }
 }
 
-// --- UTILIDADES ---
-
-fn rank_to_str(r: Rank) -> &'static str {
-    match r {
-        Rank::Ace => "A", Rank::King => "K", Rank::Queen => "Q", Rank::Jack => "J",
-        Rank::Ten => "T", Rank::Nine => "9", Rank::Eight => "8", Rank::Seven => "7",
-        Rank::Six => "6", Rank::Five => "5", Rank::Four => "4", Rank::Three => "3",
-        Rank::Two => "2",
-    }
-}
-
 impl PokerApp {
-    fn get_all_known_cards(&self) -> HashSet<MyCard> {
-        let mut known = HashSet::new();
-        // Hero
-        for c in self.hero_hand.iter().flatten() { known.insert(*c); }
+    fn get_all_known_cards(&self) -> CardSet {
+        let mut known = CardSet::empty();
+        // Hero (como rango no tiene cartas fijas)
+        if !self.hero_is_range {
+            for c in self.hero_hand.iter().flatten() { known.insert(*c); }
+        }
         // Villain
         for c in self.villain_hand.iter().flatten() { known.insert(*c); }
         // Friends
@@ -124,46 +287,116 @@ impl PokerApp {
         }
         // Board
         for c in self.board.iter().flatten() { known.insert(*c); }
+        // Jugadores del showdown
+        if self.showdown_mode {
+            for seat in &self.seats[..self.seat_count] {
+                for c in seat.cards.iter().flatten() { known.insert(*c); }
+            }
+        }
         known
     }
 
+    // Reclasifica los proyectos sólo si cambió la mesa
+    fn sync_board_draws(&mut self, board: &[MyCard]) {
+        if !self.board_draws.as_ref().is_some_and(|d| d.board == board) {
+            self.board_draws = Some(BoardDraws::new(board));
+        }
+    }
+
+    fn spot(&self) -> Spot {
+        Spot {
+            hero: self.hero_hand.iter().flatten().copied().collect(),
+            villain: self.villain_hand.iter().flatten().copied().collect(),
+            board: self.board.iter().flatten().copied().collect(),
+            dead: self.get_all_known_cards(),
+            range: self.villain_range.clone(),
+            others: self.others_use_matrix[..self.opponents - 1]
+                .iter()
+                .map(|&use_matrix| if use_matrix { self.villain_range.clone() } else { Range::full() })
+                .collect(),
+        }
+    }
+
+    fn calc_mode(&self) -> CalcMode {
+        if self.use_monte_carlo {
+            CalcMode::MonteCarlo { iterations: self.mc_iterations, seed: self.mc_seed }
+        } else {
+            CalcMode::Exhaustive
+        }
+    }
+
+    // Último resultado disponible; si cambió algo, lanza (o relanza) el cálculo en segundo plano
+    fn report(&mut self) -> Arc<EquityReport> {
+        self.engine.set_threads(self.threads);
+        self.worker.request(&self.spot(), self.calc_mode());
+        self.worker.poll();
+        self.worker.report().unwrap_or_default()
+    }
+
+    // Rango vs rango: el villano es su rango, o un único combo si tiene las cartas puestas.
+    // Las cartas muertas son sólo las de los aliados (la mesa va aparte).
+    fn range_report(&mut self) -> Arc<RangeEquityReport> {
+        let villain = match self.villain_hand {
+            [Some(c1), Some(c2)] => {
+                let mut range = Range::empty();
+                range.set_combo_weight(&[c1, c2], 1.0);
+                range
+            }
+            _ => self.villain_range.clone(),
+        };
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        let friends: CardSet = self.friends_hands.iter().flatten().flatten().copied().collect();
+        self.engine.set_threads(self.threads);
+        self.range_worker.request(&self.hero_range, &villain, &board, friends, self.calc_mode());
+        self.range_worker.poll();
+        self.range_worker.report().unwrap_or_default()
+    }
+
+    // Descripción de la mano hecha con la mesa (desde el flop)
+    fn hand_text(&self, hole: &[MyCard; 2]) -> Option<String> {
+        let mut cards: CardSet = self.board.iter().flatten().copied().collect();
+        if cards.len() < 3 || cards.intersects(CardSet::from(hole)) {
+            return None;
+        }
+        cards = cards.union(CardSet::from(hole));
+        Some(describe(self.engine.rank(cards), self.language))
+    }
+
+    // Rango que muestra y edita la matriz (el del villano, o el del hero en rango vs rango)
+    fn matrix_range(&self) -> &Range {
+        if self.hero_is_range && self.edit_hero_range { &self.hero_range } else { &self.villain_range }
+    }
+
+    fn matrix_range_mut(&mut self) -> &mut Range {
+        if self.hero_is_range && self.edit_hero_range { &mut self.hero_range } else { &mut self.villain_range }
+    }
+
     fn render_card_selector(&mut self, ui: &mut egui::Ui) {
         ui.label(egui::RichText::new("SELECCIONAR CARTA").strong().size(16.0));
         ui.separator();
         
-        let ranks = [
-            Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten, 
-            Rank::Nine, Rank::Eight, Rank::Seven, Rank::Six, 
-            Rank::Five, Rank::Four, Rank::Three, Rank::Two
-        ];
-        
-        let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+        let known_cards = self.get_all_known_cards();
 
-        egui::Grid::new("selector_grid").spacing([5.0, 5.0]).show(ui, |ui| {
-            for suit in suits {
-                for rank in ranks {
-                    let card = MyCard { rank, suit };
-                    let known = self.get_all_known_cards().contains(&card);
-                    
-                    // Si ya está usada, deshabilitamos el botón visualmente
-                    let btn_text = egui::RichText::new(card.display_text())
-                        .color(if known { egui::Color32::DARK_GRAY } else { card.color() })
-                        .size(18.0); // Cartas más grandes en selector
-                    
-                    if ui.add_enabled(!known, egui::Button::new(btn_text).min_size(egui::vec2(30.0, 40.0))).clicked() {
-                        if let Some(slot) = self.selected_card_idx {
-                            match slot {
-                                CardSlot::Hero(i) => self.hero_hand[i] = Some(card),
-                                CardSlot::Villain(i) => self.villain_hand[i] = Some(card),
-                                CardSlot::Friend(f, i) => self.friends_hands[f][i] = Some(card),
-                                CardSlot::Board(i) => self.board[i] = Some(card),
-                            }
-                            // Auto-avance simple (opcional, por ahora no para evitar confusión)
-                            self.selected_card_idx = None; 
-                        }
+        card_grid(ui, "selector_grid", |ui, card| {
+            let known = known_cards.contains(card);
+
+            // Si ya está usada, deshabilitamos el botón visualmente
+            let btn_text = egui::RichText::new(card.display_text())
+                .color(if known { egui::Color32::DARK_GRAY } else { card_color(&card) })
+                .size(18.0); // Cartas más grandes en selector
+
+            if ui.add_enabled(!known, egui::Button::new(btn_text).min_size(egui::vec2(30.0, 40.0))).clicked() {
+                if let Some(slot) = self.selected_card_idx {
+                    match slot {
+                        CardSlot::Hero(i) => self.hero_hand[i] = Some(card),
+                        CardSlot::Villain(i) => self.villain_hand[i] = Some(card),
+                        CardSlot::Friend(f, i) => self.friends_hands[f][i] = Some(card),
+                        CardSlot::Board(i) => self.board[i] = Some(card),
+                        CardSlot::Seat(s, i) => self.seats[s].cards[i] = Some(card),
                     }
+                    // Auto-avance simple (opcional, por ahora no para evitar confusión)
+                    self.selected_card_idx = None;
                 }
-                ui.end_row();
             }
         });
         
@@ -175,17 +408,521 @@ impl PokerApp {
                     CardSlot::Villain(i) => self.villain_hand[i] = None,
                     CardSlot::Friend(f, i) => self.friends_hands[f][i] = None,
                     CardSlot::Board(i) => self.board[i] = None,
+                    CardSlot::Seat(s, i) => self.seats[s].cards[i] = None,
                 }
                 self.selected_card_idx = None;
             }
         }
     }
 
+    // Editor de combos de una celda: incluir/excluir o ponderar cada combinación de palos
+    fn render_combo_editor(&mut self, ctx: &egui::Context) {
+        let Some((r1_idx, r2_idx)) = self.editing_cell else { return };
+        let known = self.get_all_known_cards();
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        let mut open = true;
+
+        egui::Window::new(format!("Combos de {}", cell_name(r1_idx, r2_idx)))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Todos").clicked() {
+                        self.matrix_range_mut().set_cell_weight(r1_idx, r2_idx, 1.0);
+                    }
+                    if ui.button("Ninguno").clicked() {
+                        self.matrix_range_mut().set_cell_weight(r1_idx, r2_idx, 0.0);
+                    }
+                    // Con la mesa: deja sólo los combos con proyecto de color o color hecho, usando alguna carta de la mano
+                    let flush_btn = ui.add_enabled(board.len() >= 3, egui::Button::new("Sólo proyectos de color"));
+                    if flush_btn.clicked() {
+                        for combo in cell_combos(r1_idx, r2_idx) {
+                            let made_flush = combo.iter().any(|h| combo.iter().chain(board.iter()).filter(|c| c.suit == h.suit).count() >= 5);
+                            if !made_flush && !Draws::classify(&combo, &board).flush_draw {
+                                self.matrix_range_mut().set_combo_weight(&combo, 0.0);
+                            }
+                        }
+                    }
+                });
+                ui.separator();
+
+                egui::Grid::new("combo_editor_grid").spacing([10.0, 4.0]).show(ui, |ui| {
+                    for combo in cell_combos(r1_idx, r2_idx) {
+                        let blocked = known.intersects(CardSet::from(&combo));
+                        ui.horizontal(|ui| {
+                            for card in combo {
+                                let color = if blocked { egui::Color32::DARK_GRAY } else { card_color(&card) };
+                                ui.label(egui::RichText::new(card.display_text()).size(16.0).color(color).strong());
+                            }
+                        });
+
+                        let mut weight = self.matrix_range().combo_weight(&combo);
+                        let mut included = weight > 0.0;
+                        if ui.checkbox(&mut included, "").changed() {
+                            weight = if included { 1.0 } else { 0.0 };
+                        }
+                        ui.add(egui::Slider::new(&mut weight, 0.0..=1.0).custom_formatter(|w, _| format!("{:.0}%", w * 100.0)));
+                        self.matrix_range_mut().set_combo_weight(&combo, weight);
+
+                        if blocked {
+                            ui.label(egui::RichText::new("Bloqueado").color(egui::Color32::GRAY));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+        if !open {
+            self.editing_cell = None;
+        }
+    }
+
+    // Quién tiene la carta (para explicar por qué un combo está bloqueado)
+    fn card_owner(&self, card: &MyCard) -> Option<String> {
+        if !self.hero_is_range && self.hero_hand.contains(&Some(*card)) {
+            return Some("Hero".to_string());
+        }
+        if self.villain_hand.contains(&Some(*card)) {
+            return Some("Villano".to_string());
+        }
+        if let Some(i) = self.friends_hands.iter().position(|f| f.contains(&Some(*card))) {
+            return Some(format!("Aliado J{}", i + 2));
+        }
+        if self.board.contains(&Some(*card)) {
+            return Some("Mesa".to_string());
+        }
+        if self.showdown_mode {
+            if let Some(i) = self.seats[..self.seat_count].iter().position(|s| s.cards.contains(&Some(*card))) {
+                return Some(format!("Jugador {}", i + 1));
+            }
+        }
+        None
+    }
+
+    // Detalle de una celda (hover): cada combo, si está bloqueado y por quién, su mano y su resultado vs hero
+    fn render_cell_detail(&self, ui: &mut egui::Ui, report: &EquityReport, r1_idx: usize, r2_idx: usize) {
+        let cell = report.cells[r1_idx][r2_idx];
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+
+        ui.label(egui::RichText::new(cell_name(r1_idx, r2_idx)).strong().size(16.0));
+        ui.label(format!("Peso en el rango: {:.0}%", self.matrix_range().cell_weight(r1_idx, r2_idx) * 100.0));
+        if cell.outcome.total() > 0.0 {
+            // Equity del hero contra esta clase de mano (preflop: contra las 169 clases)
+            let text = if self.hero_is_range && self.edit_hero_range { "Equity de la celda vs el rango del villano" } else { "Tu equity vs la celda" };
+            ui.label(egui::RichText::new(format!("{}: {:.1}%", text, cell.outcome.equity() * 100.0)).strong());
+        }
+        ui.separator();
+
+        egui::Grid::new(("cell_detail", r1_idx, r2_idx)).spacing([12.0, 2.0]).show(ui, |ui| {
+            for combo in cell_combos(r1_idx, r2_idx) {
+                ui.horizontal(|ui| {
+                    for card in combo {
+                        ui.label(egui::RichText::new(card.display_text()).color(card_color(&card)).strong());
+                    }
+                });
+
+                let owners: Vec<String> = combo.iter().filter_map(|c| self.card_owner(c)).collect();
+                if !owners.is_empty() {
+                    ui.label(egui::RichText::new(format!("Bloqueado ({})", owners.join(", "))).color(egui::Color32::GRAY));
+                    ui.label("");
+                    ui.label("");
+                    ui.end_row();
+                    continue;
+                }
+
+                let mut made_hand = self.hand_text(&combo).unwrap_or_else(|| "-".to_string());
+                let draws = Draws::classify(&combo, &board);
+                if !draws.is_empty() {
+                    made_hand = format!("{} · {}", made_hand, draws.labels().join(", "));
+                }
+                match report.combo_outcomes.get(&combo_key(&combo)) {
+                    Some(o) => {
+                        ui.label(format!("Peso {:.0}%", self.matrix_range().combo_weight(&combo) * 100.0));
+                        ui.label(made_hand);
+                        let color = if o.wins > o.losses {
+                            egui::Color32::from_rgb(50, 180, 50)
+                        } else if o.losses > o.wins {
+                            egui::Color32::from_rgb(200, 50, 50)
+                        } else {
+                            egui::Color32::from_rgb(100, 100, 255)
+                        };
+                        ui.label(egui::RichText::new(format!("Ganas {:.1}% · Pierdes {:.1}% · Empate {:.1}%", o.win_pct(), o.lose_pct(), o.tie_pct())).color(color));
+                    }
+                    None => {
+                        let status = if self.matrix_range().combo_weight(&combo) > 0.0 { "Sin calcular" } else { "Fuera de rango" };
+                        ui.label(egui::RichText::new(status).color(egui::Color32::GRAY));
+                        ui.label(made_hand);
+                        ui.label("");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    }
+
+    // Modo showdown: N jugadores con cartas o rango, equity exacta recorriendo el resto de la mesa
+    fn render_showdown(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.heading(format!("🃏 SHOWDOWN ({} jugadores)", self.seat_count));
+        ui.horizontal(|ui| {
+            ui.label("Jugadores:");
+            ui.add(egui::DragValue::new(&mut self.seat_count).clamp_range(2..=GUI_SEATS));
+        });
+        ui.separator();
+
+        // Cálculo (en segundo plano) sólo si todos los jugadores tienen mano
+        let hands: Option<Vec<SeatHand>> = self.seats[..self.seat_count]
+            .iter()
+            .map(|seat| match (seat.use_range, seat.cards) {
+                (true, _) => Some(SeatHand::Range(seat.range.clone())),
+                (false, [Some(c1), Some(c2)]) => Some(SeatHand::Cards([c1, c2])),
+                (false, _) => None,
+            })
+            .collect();
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        // Muertas: las cartas del hero, del villano y de los aliados, que siguen marcadas como usadas en el panel izquierdo
+        let hero = self.hero_hand.iter().filter(|_| !self.hero_is_range);
+        let dead: CardSet = hero.chain(&self.villain_hand).chain(self.friends_hands.iter().flatten()).flatten().copied().collect();
+        if let Some(hands) = &hands {
+            self.engine.set_threads(self.threads);
+            self.showdown_worker.request(hands, &board, dead, self.calc_mode());
+        }
+        self.showdown_worker.poll();
+        if self.showdown_worker.is_running() {
+            ui.horizontal(|ui| {
+                ui.spinner();
+                ui.add(egui::ProgressBar::new(self.showdown_worker.progress()).show_percentage().desired_width(200.0));
+                ui.label(egui::RichText::new("Calculando... (resultados anteriores)").color(egui::Color32::YELLOW));
+            });
+            ctx.request_repaint();
+        }
+        let report = self.showdown_worker.report().filter(|r| r.seats.len() == self.seat_count);
+
+        egui::Grid::new("showdown_grid").spacing([15.0, 8.0]).striped(true).show(ui, |ui| {
+            ui.label("");
+            ui.label("Mano");
+            ui.label(egui::RichText::new("Victoria").strong());
+            ui.label(egui::RichText::new("Empate").strong());
+            ui.label(egui::RichText::new("Equity (bote)").strong());
+            ui.end_row();
+
+            for s in 0..self.seat_count {
+                ui.vertical(|ui| {
+                    ui.label(egui::RichText::new(format!("Jugador {}", s + 1)).strong());
+                    ui.radio_value(&mut self.seats[s].use_range, false, "Cartas");
+                    ui.radio_value(&mut self.seats[s].use_range, true, "Rango");
+                });
+                ui.vertical(|ui| {
+                    if self.seats[s].use_range {
+                        let seat = &mut self.seats[s];
+                        let response = ui.add(egui::TextEdit::singleline(&mut seat.range_text).hint_text("ej: QQ+, AKs").desired_width(180.0));
+                        if response.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                            match seat.range_text.parse::<Range>() {
+                                Ok(range) => {
+                                    seat.range = range;
+                                    seat.error = None;
+                                }
+                                Err(e) => seat.error = Some(e),
+                            }
+                        }
+                        match &seat.error {
+                            Some(error) => ui.label(egui::RichText::new(error).color(egui::Color32::RED)),
+                            None => ui.label(egui::RichText::new(seat.range.to_string()).size(10.0)),
+                        };
+                    } else {
+                        ui.horizontal(|ui| {
+                            self.render_slot(ui, self.seats[s].cards[0], CardSlot::Seat(s, 0), "C1");
+                            self.render_slot(ui, self.seats[s].cards[1], CardSlot::Seat(s, 1), "C2");
+                        });
+                    }
+                });
+                match report.as_ref().map(|r| r.seats[s]) {
+                    Some(result) => {
+                        ui.label(egui::RichText::new(format!("{:.2}%", result.win * 100.0)).color(egui::Color32::GREEN));
+                        ui.label(egui::RichText::new(format!("{:.2}%", result.tie * 100.0)).color(egui::Color32::from_rgb(100, 100, 255)));
+                        ui.label(egui::RichText::new(format!("{:.2}%", result.equity * 100.0)).strong().size(16.0));
+                    }
+                    None => {
+                        ui.label("-");
+                        ui.label("-");
+                        ui.label("-");
+                    }
+                }
+                ui.end_row();
+            }
+        });
+
+        ui.add_space(10.0);
+        if hands.is_none() {
+            ui.label(egui::RichText::new("⚠️ FALTAN CARTAS PARA CALCULAR").color(egui::Color32::YELLOW));
+        } else if let Some(report) = &report {
+            if report.sampled {
+                ui.label(format!("Demasiadas combinaciones para recorrerlas todas: muestra Monte Carlo de {:.0} showdowns.", report.showdowns));
+            } else {
+                ui.label("Exacto: se recorren todas las mesas posibles (y todos los combos de los rangos).");
+            }
+        }
+        ui.label("La mesa y las cartas muertas (hero, villano y aliados) se toman del panel izquierdo. Rango: escribe y pulsa Enter.");
+    }
+
+    // Duelo 1 vs 1 en flop/turn: equity exacta y qué turn/river gana, pierde o empata el hero
+    fn render_duel(&mut self, ui: &mut egui::Ui) {
+        let (Some(h1), Some(h2), Some(v1), Some(v2)) = (self.hero_hand[0], self.hero_hand[1], self.villain_hand[0], self.villain_hand[1]) else { return };
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        if board.len() < 3 {
+            return;
+        }
+        let friends: CardSet = self.friends_hands.iter().flatten().flatten().copied().collect();
+        let key = ([h1, h2], [v1, v2], board.clone(), friends);
+        if !matches!(&self.duel_cache, Some((cached, _)) if *cached == key) {
+            let duel = self.engine.duel(&[h1, h2], &[v1, v2], &board, friends);
+            self.duel_cache = Some((key, duel));
+        }
+        let Some((_, duel)) = &self.duel_cache else { return };
+        let t = duel.totals;
+        ui.label(egui::RichText::new(format!("Equity exacta: {:.2}%", t.equity() * 100.0)).size(20.0).strong());
+        ui.label(format!("{:.0} runouts: ganas {:.0} ({:.1}%) · pierdes {:.0} ({:.1}%) · empate {:.0} ({:.1}%)", t.total(), t.wins, t.win_pct(), t.losses, t.lose_pct(), t.ties, t.tie_pct()));
+        if duel.next_cards.is_empty() {
+            return;
+        }
+
+        ui.add_space(5.0);
+        if board.len() == 3 {
+            ui.label(egui::RichText::new("TURN (resultado sobre todos los river)").strong());
+            if let Some(turn) = render_next_cards(ui, &duel.next_cards, true) {
+                self.duel_turn = Some(turn);
+            }
+            // River tras el turn elegido (si sigue siendo una carta libre)
+            let turn = self.duel_turn.filter(|t| duel.next_cards.iter().any(|(c, _)| c == t));
+            if let Some(turn) = turn {
+                let with_turn: Vec<MyCard> = board.iter().copied().chain([turn]).collect();
+                let key = ([h1, h2], [v1, v2], with_turn.clone(), friends);
+                if !matches!(&self.river_cache, Some((cached, _)) if *cached == key) {
+                    let rivers = self.engine.duel(&[h1, h2], &[v1, v2], &with_turn, friends);
+                    self.river_cache = Some((key, rivers));
+                }
+                let Some((_, rivers)) = &self.river_cache else { return };
+                ui.horizontal(|ui| {
+                    ui.label(egui::RichText::new("RIVER tras el turn").strong());
+                    ui.label(egui::RichText::new(turn.display_text()).color(card_color(&turn)).strong());
+                    ui.label(format!("(equity {:.1}%)", rivers.totals.equity() * 100.0));
+                });
+                render_next_cards(ui, &rivers.next_cards, false);
+            } else {
+                ui.label(egui::RichText::new("Click en un turn para ver cada river.").color(egui::Color32::GRAY));
+            }
+        } else {
+            ui.label(egui::RichText::new("RIVER").strong());
+            render_next_cards(ui, &duel.next_cards, false);
+        }
+    }
+
+    // Outs del hero en flop/turn agrupadas por la mano que hacen (limpias en verde, manchadas en ámbar)
+    fn render_outs(&mut self, ui: &mut egui::Ui) {
+        if self.hero_is_range || self.opponents > 1 {
+            return;
+        }
+        let spot = self.spot();
+        if !matches!(&self.outs_cache, Some((cached, _)) if *cached == spot) {
+            let outs = self.engine.outs(&spot);
+            self.outs_cache = Some((spot, outs));
+        }
+        let Some((_, Some(report))) = &self.outs_cache else { return };
+
+        ui.label(egui::RichText::new("🎯 OUTS").strong().size(16.0));
+        if !report.is_behind() {
+            ui.label(format!("Vas por delante si se mostraran las manos ahora ({:.1}% equity): no necesitas outs.", report.equity * 100.0));
+            return;
+        }
+        ui.label(format!(
+            "Vas por detrás ahora ({:.1}% equity). {} outs ({} limpias) de {} cartas no vistas: {:.1}% de ligar en la próxima carta.",
+            report.equity * 100.0,
+            report.outs.len(),
+            report.clean_count(),
+            report.unseen,
+            report.hit_chance() * 100.0
+        ));
+
+        let mut categories: Vec<_> = report.outs.iter().map(|o| o.category).collect();
+        categories.sort();
+        categories.dedup();
+        egui::Grid::new("outs_grid").spacing([10.0, 4.0]).show(ui, |ui| {
+            for category in categories.into_iter().rev() {
+                let outs: Vec<_> = report.outs.iter().filter(|o| o.category == category).collect();
+                ui.label(egui::RichText::new(format!("{} ({})", category.name(), outs.len())).strong());
+                ui.horizontal_wrapped(|ui| {
+                    for out in outs {
+                        let stroke = if out.is_clean() { egui::Color32::from_rgb(50, 180, 50) } else { egui::Color32::from_rgb(210, 160, 40) };
+                        let button = egui::Button::new(egui::RichText::new(out.card.display_text()).color(card_color(&out.card)).strong()).fill(egui::Color32::from_gray(30)).stroke(egui::Stroke::new(1.0, stroke));
+                        let hover = if out.is_clean() {
+                            format!("Limpia · equity con la carta {:.1}%", out.equity * 100.0)
+                        } else {
+                            format!("Manchada: mejora al {:.1}% del rango del villano por encima tuyo · equity con la carta {:.1}%", out.tainted * 100.0, out.equity * 100.0)
+                        };
+                        ui.add(button).on_hover_text(hover);
+                    }
+                });
+                ui.end_row();
+            }
+        });
+        ui.label(egui::RichText::new("Borde verde = out limpia; ámbar = manchada (también mejora manos del villano que te ganan). Las cartas de los aliados no cuentan.").color(egui::Color32::GRAY));
+    }
+
+    // Fuerza relativa del hero: puesto entre las manos posibles (nuts = 1ª) y las manos que le ganan
+    fn render_strength(&mut self, ui: &mut egui::Ui) {
+        let ([Some(c1), Some(c2)], false) = (self.hero_hand, self.hero_is_range) else { return };
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        let key = ([c1, c2], board, self.get_all_known_cards());
+        if !matches!(&self.strength_cache, Some((cached, _)) if *cached == key) {
+            let strength = self.engine.hand_strength(&key.0, &key.1, key.2);
+            self.strength_cache = Some((key, strength));
+        }
+        let Some((_, Some(strength))) = &self.strength_cache else { return };
+
+        let position = if strength.is_nuts() { "🥇 NUTS".to_string() } else { format!("{}ª nuts", strength.nut_rank) };
+        ui.label(
+            egui::RichText::new(format!("{} · ganas al {:.1}% de los combos vivos (empatas con {})", position, strength.beats() * 100.0, strength.tied))
+                .strong()
+                .size(16.0),
+        );
+        if strength.better.is_empty() {
+            return;
+        }
+        let losing: usize = strength.better.iter().map(|h| h.combos.len()).sum();
+        ui.collapsing(format!("Manos que te ganan ({} combos)", losing), |ui| {
+            egui::ScrollArea::vertical().max_height(200.0).show(ui, |ui| {
+                egui::Grid::new("better_hands").spacing([12.0, 2.0]).striped(true).show(ui, |ui| {
+                    for hand in &strength.better {
+                        // Celdas de la matriz que tienen esa mano (ej: "AKs, AKo")
+                        let mut cells: Vec<(usize, usize)> = hand.combos.iter().map(combo_cell).collect();
+                        cells.sort();
+                        cells.dedup();
+                        let names: Vec<String> = cells.iter().map(|&(r1, r2)| cell_name(r1, r2)).collect();
+                        ui.label(describe(hand.rank, self.language));
+                        ui.label(names.join(", "));
+                        ui.label(format!("{} combos", hand.combos.len()));
+                        ui.end_row();
+                    }
+                });
+            });
+        });
+    }
+
+    // Mapa de calor del próximo turn (en el flop) o river (en el turn): equity del hero tras cada carta
+    fn render_next_card_heatmap(&mut self, ui: &mut egui::Ui, ctx: &egui::Context, current_equity: f64) {
+        let board_len = self.board.iter().flatten().count();
+        if self.hero_is_range || self.opponents > 1 || !(3..=4).contains(&board_len) {
+            return;
+        }
+        self.engine.set_threads(self.threads);
+        self.next_card_worker.request(&self.spot());
+        self.next_card_worker.poll();
+
+        let street = if board_len == 3 { "TURN" } else { "RIVER" };
+        ui.horizontal(|ui| {
+            ui.label(egui::RichText::new(format!("🔥 EQUITY SEGÚN EL {}", street)).strong().size(16.0));
+            ui.label(format!("(ahora {:.1}%)", current_equity * 100.0));
+            if self.next_card_worker.is_running() {
+                ui.spinner();
+                ui.add(egui::ProgressBar::new(self.next_card_worker.progress()).show_percentage().desired_width(120.0));
+                ctx.request_repaint();
+            }
+        });
+
+        let known = self.get_all_known_cards();
+        let report = self.next_card_worker.report();
+        card_grid(ui, "next_card_heatmap", |ui, card| {
+            let outcome = report.as_ref().and_then(|r| r.outcome(card)).filter(|_| !known.contains(card));
+            let (fill, text_color) = match outcome {
+                Some(o) => (equity_color(o.equity()), egui::Color32::WHITE),
+                None => (egui::Color32::from_rgb(30, 30, 30), egui::Color32::DARK_GRAY),
+            };
+            let button = egui::Button::new(egui::RichText::new(card.display_text()).color(text_color).strong()).fill(fill).min_size(egui::vec2(30.0, 28.0));
+            let response = ui.add(button);
+            if let Some(o) = outcome {
+                let change = (o.equity() - current_equity) * 100.0;
+                response.on_hover_text(format!("Equity {:.1}% ({:+.1}) · Ganas {:.1}% · Pierdes {:.1}% · Empate {:.1}%", o.equity() * 100.0, change, o.win_pct(), o.lose_pct(), o.tie_pct()));
+            }
+        });
+        ui.label(egui::RichText::new("Rojo = carta que asusta, verde = carta buena. En gris, las cartas que ya están en juego.").color(egui::Color32::GRAY));
+    }
+
+    // Proyectos del hero y del villano (mano concreta o reparto ponderado de su rango) en flop/turn
+    fn render_draws(&mut self, ui: &mut egui::Ui) {
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        if !(3..=4).contains(&board.len()) {
+            return;
+        }
+        self.sync_board_draws(&board);
+        let Some(board_draws) = &self.board_draws else { return };
+        let describe = |draws: Draws| if draws.is_empty() { "ninguno".to_string() } else { draws.labels().join(" · ") };
+        if let (false, [Some(c1), Some(c2)]) = (self.hero_is_range, self.hero_hand) {
+            ui.label(format!("Tus proyectos: {}", describe(board_draws.get(&[c1, c2]))));
+        }
+        if let [Some(c1), Some(c2)] = self.villain_hand {
+            ui.label(format!("Proyectos del villano: {}", describe(board_draws.get(&[c1, c2]))));
+            return;
+        }
+
+        let known = self.get_all_known_cards();
+        let combos: Vec<([MyCard; 2], f64)> = self.villain_range.weighted_combos().into_iter().filter(|(c, _)| !known.intersects(CardSet::from(c))).collect();
+        let total: f64 = combos.iter().map(|(_, w)| w).sum();
+        if total <= 0.0 {
+            return;
+        }
+        let draws: Vec<(Draws, f64)> = combos.iter().map(|(c, w)| (board_draws.get(c), *w)).collect();
+        let shares: Vec<String> = DrawKind::ALL
+            .iter()
+            .filter_map(|kind| {
+                let weight: f64 = draws.iter().filter(|(d, _)| d.has(*kind)).map(|(_, w)| w).sum();
+                (weight > 0.0).then(|| format!("{} {:.1}%", kind.name(), weight / total * 100.0))
+            })
+            .collect();
+        ui.label(format!("Rango del villano con proyecto: {}", if shares.is_empty() { "ninguno".to_string() } else { shares.join(" · ") }));
+    }
+
+    // Reparto del rango de la matriz por mano hecha (estilo Flopzilla), ponderado por el peso de cada combo
+    fn render_breakdown(&mut self, ui: &mut egui::Ui) {
+        let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+        let known = self.get_all_known_cards();
+        if board.len() >= 3 && !matches!(&self.breakdown_cache, Some(((r, b, k), _)) if r == self.matrix_range() && *b == board && *k == known) {
+            let breakdown = self.engine.range_breakdown(self.matrix_range(), &board, known);
+            self.breakdown_cache = Some(((self.matrix_range().clone(), board.clone(), known), breakdown));
+        }
+        ui.vertical(|ui| {
+            ui.label(egui::RichText::new("MANOS HECHAS DEL RANGO").strong());
+            let Some((_, breakdown)) = self.breakdown_cache.as_ref().filter(|_| board.len() >= 3) else {
+                ui.label(egui::RichText::new("Disponible desde el flop.").color(egui::Color32::GRAY));
+                return;
+            };
+            if breakdown.total <= 0.0 {
+                ui.label(egui::RichText::new("Sin combos vivos en el rango.").color(egui::Color32::GRAY));
+                return;
+            }
+            egui::Grid::new("breakdown_grid").spacing([12.0, 3.0]).striped(true).show(ui, |ui| {
+                ui.label(egui::RichText::new("Mano").strong());
+                ui.label(egui::RichText::new("Combos").strong());
+                ui.label(egui::RichText::new("%").strong());
+                ui.end_row();
+                for (made, weight) in &breakdown.rows {
+                    ui.label(made.name());
+                    ui.label(format!("{:.1}", weight));
+                    ui.label(format!("{:.1}%", weight / breakdown.total * 100.0));
+                    ui.end_row();
+                }
+                ui.label(egui::RichText::new("Total").strong());
+                ui.label(egui::RichText::new(format!("{:.1}", breakdown.total)).strong());
+                ui.label("100%");
+                ui.end_row();
+            });
+        });
+    }
+
     fn render_slot(&mut self, ui: &mut egui::Ui, card: Option<MyCard>, slot: CardSlot, label: &str) {
         let is_selected = self.selected_card_idx == Some(slot);
         
         let (text, color, bg) = if let Some(c) = card {
-            (c.display_text(), c.color(), egui::Color32::from_gray(40))
+            (c.display_text(), card_color(&c), egui::Color32::from_gray(40))
         } else {
             ("?".to_string(), egui::Color32::GRAY, egui::Color32::from_gray(20))
         };
@@ -212,14 +949,36 @@ impl eframe::App for PokerApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::SidePanel::left("left_panel").min_width(300.0).show(ctx, |ui| {
             ui.heading("🎰 CONTROLES");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut self.showdown_mode, false, "Equity vs rango");
+                ui.radio_value(&mut self.showdown_mode, true, "Showdown (N jugadores)");
+            });
+            ui.horizontal(|ui| {
+                ui.label("Manos en:");
+                ui.radio_value(&mut self.language, Language::Spanish, "Español");
+                ui.radio_value(&mut self.language, Language::English, "English");
+            });
             ui.separator();
 
             // HERO
             ui.horizontal(|ui| {
                 ui.label("HERO:");
-                self.render_slot(ui, self.hero_hand[0], CardSlot::Hero(0), "C1");
-                self.render_slot(ui, self.hero_hand[1], CardSlot::Hero(1), "C2");
+                ui.radio_value(&mut self.hero_is_range, false, "Mano");
+                ui.radio_value(&mut self.hero_is_range, true, "Rango");
             });
+            if self.hero_is_range {
+                ui.label(format!("Rango del hero ({:.1} combos): se edita en la matriz.", self.hero_range.weighted_combos().iter().map(|(_, w)| w).sum::<f64>()));
+            } else {
+                ui.horizontal(|ui| {
+                    self.render_slot(ui, self.hero_hand[0], CardSlot::Hero(0), "C1");
+                    self.render_slot(ui, self.hero_hand[1], CardSlot::Hero(1), "C2");
+                });
+                if let [Some(c1), Some(c2)] = self.hero_hand {
+                    if let Some(text) = self.hand_text(&[c1, c2]) {
+                        ui.label(egui::RichText::new(text).strong().color(egui::Color32::LIGHT_BLUE));
+                    }
+                }
+            }
             ui.separator();
 
             // BOARD
@@ -243,6 +1002,23 @@ impl eframe::App for PokerApp {
             });
             ui.separator();
 
+            // RIVALES (MULTIWAY)
+            ui.collapsing("🎯 Rivales en la mano", |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Rivales:");
+                    ui.add(egui::DragValue::new(&mut self.opponents).clamp_range(1..=9));
+                });
+                ui.label("El rival 1 es el villano (cartas o matriz).");
+                for i in 0..self.opponents - 1 {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("Rival {}:", i + 2));
+                        ui.radio_value(&mut self.others_use_matrix[i], false, "Aleatorio");
+                        ui.radio_value(&mut self.others_use_matrix[i], true, "Rango de la matriz");
+                    });
+                }
+            });
+            ui.separator();
+
             // ALIADOS
             ui.collapsing("👥 Aliados (Blockers)", |ui| {
                 for i in 0..3 {
@@ -253,6 +1029,33 @@ impl eframe::App for PokerApp {
                     });
                 }
             });
+            ui.separator();
+
+            // MODO DE CÁLCULO
+            ui.collapsing("⚙️ Cálculo", |ui| {
+                let exhaustive = if self.opponents > 1 {
+                    // Con varios rivales el modo exhaustivo no recorre las manos rivales: es Monte Carlo con semilla fija
+                    format!("Exhaustivo (con {} rivales: Monte Carlo de {} iteraciones, semilla fija)", self.opponents, MULTIWAY_ITERATIONS)
+                } else {
+                    format!("Exhaustivo (todos los runouts; preflop con aliados: estimación con {} mesas)", PREFLOP_BOARDS)
+                };
+                ui.radio_value(&mut self.use_monte_carlo, false, exhaustive);
+                ui.radio_value(&mut self.use_monte_carlo, true, "Monte Carlo (muestreo)");
+                ui.add_enabled_ui(self.use_monte_carlo, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label("Iteraciones:");
+                        ui.add(egui::DragValue::new(&mut self.mc_iterations).clamp_range(100..=1_000_000).speed(100));
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Semilla:");
+                        ui.add(egui::DragValue::new(&mut self.mc_seed));
+                    });
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Hilos:");
+                    ui.add(egui::DragValue::new(&mut self.threads).clamp_range(1..=64));
+                });
+            });
 
             ui.add_space(20.0);
             
@@ -267,116 +1070,90 @@ impl eframe::App for PokerApp {
             }
         });
 
+        self.render_combo_editor(ctx);
+
         egui::CentralPanel::default().show(ctx, |ui| {
-            // --- 1. CÁLCULO DE ESTADÍSTICAS GLOBALES ---
-            let known_cards = self.get_all_known_cards();
-            let ranks = [
-                Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten, 
-                Rank::Nine, Rank::Eight, Rank::Seven, Rank::Six, 
-                Rank::Five, Rank::Four, Rank::Three, Rank::Two
-            ];
-            
-            let board_cards: Vec<Card> = self.board.iter().flatten().map(|c| c.to_poker_card()).collect();
-            let hero_cards: Vec<Card> = self.hero_hand.iter().flatten().map(|c| c.to_poker_card()).collect();
-
-            // Evaluar Hero
-            let hero_score = if hero_cards.len() == 2 && board_cards.len() >= 3 {
-                let mut all_hero = hero_cards.clone();
-                all_hero.extend(board_cards.clone());
-                if let Ok(rank) = self.evaluator.evaluate(&all_hero) {
-                    Some(rank)
-                } else { None }
-            } else { None };
-
-            // Chequear si hay un villano específico
-            let villain_cards: Vec<Card> = self.villain_hand.iter().flatten().map(|c| c.to_poker_card()).collect();
-            let is_1v1 = villain_cards.len() == 2;
-
-            // Variables para contadores totales
-            let mut total_possible_hands = 0;
-            let mut total_winning = 0; // Manos que YO gano
-            let mut total_losing = 0;  // Manos que ME ganan
-            let mut total_ties = 0;
-
-            // Bucle de cálculo
-            let suits = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
-            
-            if hero_score.is_some() {
-                if is_1v1 {
-                    // CÁLCULO 1vs1
-                    let mut all_villain = villain_cards.clone();
-                    all_villain.extend(board_cards.clone());
-                    
-                    if let Ok(v_score) = self.evaluator.evaluate(&all_villain) {
-                         if let Some(h_s) = hero_score {
-                            total_possible_hands = 1;
-                            if v_score > h_s { total_losing = 1; }
-                            else if v_score < h_s { total_winning = 1; }
-                            else { total_ties = 1; }
-                        }
-                    }
-                } else {
-                    // CÁLCULO VS RANGO (DECK RESTANTE)
-                    for r1 in ranks.iter() {
-                        for r2 in ranks.iter() {
-                            // Iterar palos para generar cartas específicas
-                            for s1 in suits {
-                                for s2 in suits {
-                                    // Evitar duplicados y cartas imposibles (mismo slot)
-                                    let c1 = MyCard { rank: *r1, suit: s1 };
-                                    let c2 = MyCard { rank: *r2, suit: s2 };
-                                    
-                                    // Regla: C1 debe ser "mayor" o igual a C2 para no contar doble (AhKd es lo mismo que KdAh)
-                                    // Usamos una comparación simple de bytes o orden
-                                    if c1.to_poker_card() <= c2.to_poker_card() { continue; }
-
-                                    // Si alguna carta ya es conocida (mia, amigos, mesa), esta mano es IMPOSIBLE
-                                    if known_cards.contains(&c1) || known_cards.contains(&c2) { continue; }
-
-                                    // Si llegamos acá, es una mano POSIBLE que puede tener un oponente
-                                    total_possible_hands += 1;
-
-                                    let mut op_hand = vec![c1.to_poker_card(), c2.to_poker_card()];
-                                    op_hand.extend(board_cards.clone());
-
-                                    if let Ok(op_score) = self.evaluator.evaluate(&op_hand) {
-                                        if let Some(h_s) = hero_score {
-                                            if op_score > h_s { total_losing += 1; } // Villano Mayor = Villano Gana
-                                            else if op_score < h_s { total_winning += 1; } // Villano Menor = Villano Pierde
-                                            else { total_ties += 1; }
-                                        }
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
+            if self.showdown_mode {
+                self.render_showdown(ui, ctx);
+                return;
             }
 
+            // --- 1. CÁLCULO DE ESTADÍSTICAS GLOBALES ---
+            // Rango vs rango: la matriz muestra el lado que se está editando
+            let range_report = self.hero_is_range.then(|| self.range_report());
+            let report = match &range_report {
+                Some(r) if self.edit_hero_range => Arc::new(r.hero.clone()),
+                Some(r) => Arc::new(r.villain.clone()),
+                None => self.report(),
+            };
+            // Con las 2 cartas del hero ya se calcula (preflop incluido)
+            let hero_ready = if self.hero_is_range {
+                !self.hero_range.weighted_combos().is_empty()
+            } else {
+                self.hero_hand.iter().all(|c| c.is_some())
+            };
+            let is_preflop = self.board.iter().flatten().count() < 3;
+            let is_1v1 = report.is_1v1;
+
+            // Variables para contadores totales (equity acumulada sobre todos los turn/river posibles)
+            let total_possible_hands = report.totals.total();
+            let total_winning = report.totals.wins; // Manos que YO gano
+            let total_losing = report.totals.losses;  // Manos que ME ganan
+            let total_ties = report.totals.ties;
+
             // --- 2. DASHBOARD DE PROBABILIDADES ---
-            if is_1v1 {
+            if self.hero_is_range {
+                 ui.heading("📊 RANGO VS RANGO (Hero vs Villano)");
+            } else if is_1v1 {
                  ui.heading("⚔️ DUELO 1 vs 1 (Hero vs Villano)");
+            } else if report.opponents > 1 {
+                 ui.heading(format!("📊 TUS POSIBILIDADES CONTRA {} RIVALES", report.opponents));
             } else {
                  ui.heading("📊 TUS POSIBILIDADES (Cálculo Real)");
             }
             ui.add_space(5.0);
+
+            // Progreso del cálculo en segundo plano (mientras tanto se muestran los resultados anteriores)
+            let (running, progress) = if self.hero_is_range {
+                (self.range_worker.is_running(), self.range_worker.progress())
+            } else {
+                (self.worker.is_running(), self.worker.progress())
+            };
+            if running {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.add(egui::ProgressBar::new(progress).show_percentage().desired_width(200.0));
+                    ui.label(egui::RichText::new("Calculando... (resultados anteriores)").color(egui::Color32::YELLOW));
+                });
+                ctx.request_repaint();
+            }
             
-            if hero_score.is_some() && total_possible_hands > 0 {
+            if hero_ready && total_possible_hands > 0.0 {
                 if is_1v1 {
-                    if total_winning > 0 {
+                    if total_winning > total_losing {
                         ui.label(egui::RichText::new("¡GANAS TU! 🏆").size(30.0).strong().color(egui::Color32::GREEN));
-                    } else if total_losing > 0 {
+                    } else if total_losing > total_winning {
                          ui.label(egui::RichText::new("¡PIERDES! 💀").size(30.0).strong().color(egui::Color32::RED));
                     } else {
                          ui.label(egui::RichText::new("¡EMPATE! 🤝").size(30.0).strong().color(egui::Color32::from_rgb(100, 100, 255)));
                     }
+                    if let ([Some(h1), Some(h2)], [Some(v1), Some(v2)]) = (self.hero_hand, self.villain_hand) {
+                        if let (Some(hero), Some(villain)) = (self.hand_text(&[h1, h2]), self.hand_text(&[v1, v2])) {
+                            ui.label(egui::RichText::new(format!("Hero: {}   vs   Villano: {}", hero, villain)).size(16.0));
+                        }
+                    }
+                    self.render_duel(ui);
                 } else {
-                    let win_pct = (total_winning as f32 / total_possible_hands as f32) * 100.0;
-                    let lose_pct = (total_losing as f32 / total_possible_hands as f32) * 100.0;
-                    let tie_pct = (total_ties as f32 / total_possible_hands as f32) * 100.0;
+                    let win_pct = report.totals.win_pct();
+                    let tie_pct = report.totals.tie_pct();
 
                     ui.horizontal(|ui| {
                         ui.label(egui::RichText::new(format!("TIENES UN {:.1}% DE VICTORIA", win_pct)).size(20.0).strong().color(egui::Color32::GREEN));
+                        ui.label(egui::RichText::new(format!("+ {:.1}% DE EMPATE", tie_pct)).size(16.0).color(egui::Color32::from_rgb(100, 100, 255)));
+                        ui.label(format!("= {:.1}% equity", report.totals.equity() * 100.0));
+                        if let Some(std_error) = report.std_error {
+                            ui.label(format!("(± {:.2}% error estándar)", std_error * 100.0));
+                        }
                     });
                     
                     // Barra de progreso visual
@@ -393,161 +1170,225 @@ impl eframe::App for PokerApp {
                         let safe_pct = win_pct + tie_pct;
                         let safe_width = (safe_pct / 100.0) * width;
                         let safe_rect = egui::Rect::from_min_size(rect.min, egui::vec2(safe_width, height));
-                        painter.rect_filled(safe_rect, 5.0, egui::Color32::from_rgb(50, 180, 50));
+                        painter.rect_filled(safe_rect, 5.0, egui::Color32::from_rgb(50, 100, 200));
+
+                        let win_width = (win_pct / 100.0) * width;
+                        let win_rect = egui::Rect::from_min_size(rect.min, egui::vec2(win_width, height));
+                        painter.rect_filled(win_rect, 5.0, egui::Color32::from_rgb(50, 180, 50));
                     }
 
                     ui.horizontal(|ui| {
-                        ui.label(format!("Manos que te ganan: {}", total_losing));
+                        ui.label(format!("Manos que te ganan: {:.1}", total_losing));
                         ui.label("|");
-                        ui.label(format!("Manos que ganas: {}", total_winning));
+                        ui.label(format!("Manos que ganas: {:.1}", total_winning));
                         ui.label("|");
-                        ui.label(format!("Empates: {}", total_ties));
+                        ui.label(format!("Empates: {:.1}", total_ties));
                     });
-                    ui.label("Esta barra muestra tu fuerza contra CUALQUIER mano aleatoria que pueda tener un rival.");
+                    if self.hero_is_range {
+                        ui.label("Equity del rango del hero contra el rango del villano (o sus cartas), ponderada por los dos rangos. Los rivales extra no cuentan en este modo.");
+                    } else if report.opponents > 1 {
+                        ui.label("Victoria = ganas el bote solo; empate = lo repartes con al menos un rival. La equity cuenta tu parte de los botes repartidos (muestreo Monte Carlo).");
+                    } else if is_preflop {
+                        ui.label(match report.std_error {
+                            None => "Esta barra muestra tu equity preflop exacta contra el rango del villano (matriz), sobre todas las mesas posibles.".to_string(),
+                            Some(_) if self.use_monte_carlo => "Esta barra muestra una estimación Monte Carlo de tu equity preflop contra el rango del villano (matriz).".to_string(),
+                            // Con cartas de aliados fuera del mazo no vale la tabla exacta
+                            Some(_) => format!("Esta barra muestra una estimación de tu equity preflop contra el rango del villano (matriz), sobre una muestra fija de {} mesas completas (las cartas de los aliados cambian el mazo).", PREFLOP_BOARDS),
+                        });
+                    } else {
+                        ui.label("Esta barra muestra tu equity contra el rango del villano (matriz), contando todos los turn/river posibles.");
+                    }
                 }
-            } else if hero_score.is_none() {
+            } else if !hero_ready {
                  ui.label(egui::RichText::new("⚠️ FALTAN CARTAS PARA CALCULAR").color(egui::Color32::YELLOW));
             }
+            if hero_ready {
+                ui.add_space(5.0);
+                self.render_strength(ui);
+                self.render_outs(ui);
+            }
+            self.render_draws(ui);
+            if hero_ready && total_possible_hands > 0.0 {
+                ui.add_space(5.0);
+                self.render_next_card_heatmap(ui, ctx, report.totals.equity());
+            }
 
             ui.separator();
 
             // MATRIZ HEADER
             ui.horizontal(|ui| {
-                ui.heading("DETALLE DE MANOS RIVALES");
+                if self.hero_is_range && self.edit_hero_range {
+                    ui.heading("DETALLE DEL RANGO DEL HERO");
+                } else {
+                    ui.heading("DETALLE DE MANOS RIVALES");
+                }
+                ui.label(format!("({:.1} combos en rango)", report.range_combos));
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    egui::ComboBox::from_id_source("draw_filter")
+                        .selected_text(self.draw_filter.map_or("Todas las manos", |k| k.name()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.draw_filter, None, "Todas las manos");
+                            for kind in DrawKind::ALL {
+                                ui.selectable_value(&mut self.draw_filter, Some(kind), kind.name());
+                            }
+                        });
+                    ui.label("Proyectos:");
+                    ui.checkbox(&mut self.gradient_colors, "Gradiente por equity");
                     ui.label(egui::RichText::new("⬛ Imposible").color(egui::Color32::DARK_GRAY));
-                    ui.label(egui::RichText::new("🟩 Ganas").color(egui::Color32::GREEN));
-                    ui.label(egui::RichText::new("🟥 Pierdes").color(egui::Color32::RED));
+                    if self.gradient_colors {
+                        ui.label(egui::RichText::new("100% 🟩").color(equity_color(1.0)));
+                        ui.label(egui::RichText::new("50% 🟨").color(equity_color(0.5)));
+                        ui.label(egui::RichText::new("🟥 0%").color(equity_color(0.0)));
+                    } else {
+                        ui.label(egui::RichText::new("🟩 Ganas").color(egui::Color32::GREEN));
+                        ui.label(egui::RichText::new("🟥 Pierdes").color(egui::Color32::RED));
+                    }
                 });
             });
+
+            // Rango vs rango: la misma matriz edita el rango del hero o el del villano
+            if self.hero_is_range {
+                ui.horizontal(|ui| {
+                    ui.label("Editar:");
+                    ui.radio_value(&mut self.edit_hero_range, false, "Rango del villano");
+                    ui.radio_value(&mut self.edit_hero_range, true, "Rango del hero");
+                    if self.edit_hero_range {
+                        ui.label(egui::RichText::new("(cada celda: su equity contra el rango del villano)").color(egui::Color32::GRAY));
+                    }
+                });
+            }
+
+            // RANGO EN TEXTO (entrada / salida)
+            ui.horizontal(|ui| {
+                ui.label("Rango:");
+                let edit = ui.add(egui::TextEdit::singleline(&mut self.range_text).hint_text("22+, A2s+, KTo+, QJs, T9s-54s, AKo:0.5").desired_width(300.0));
+                let enter = edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                if ui.button("Aplicar").clicked() || enter {
+                    match self.range_text.parse::<Range>() {
+                        Ok(range) => {
+                            *self.matrix_range_mut() = range;
+                            self.range_error = None;
+                        }
+                        Err(err) => self.range_error = Some(err),
+                    }
+                }
+                if ui.button("📋 Copiar rango actual").clicked() {
+                    self.range_text = self.matrix_range().to_string();
+                    ui.output_mut(|o| o.copied_text = self.range_text.clone());
+                    self.range_error = None;
+                }
+                ui.separator();
+                ui.label("Peso al hacer click:");
+                ui.add(egui::Slider::new(&mut self.brush_weight, 0.0..=1.0).custom_formatter(|w, _| format!("{:.0}%", w * 100.0)));
+            });
+            if let Some(err) = &self.range_error {
+                ui.label(egui::RichText::new(format!("⚠️ {}", err)).color(egui::Color32::YELLOW));
+            }
             ui.separator();
 
-            egui::Grid::new("poker_matrix").spacing([2.0, 2.0]).show(ui, |ui| {
-                // Header (Ranks)
-                ui.label(""); // Esquina vacía
-                for r in ranks {
-                    ui.label(egui::RichText::new(rank_to_str(r)).strong().size(16.0));
+            // Filtro de proyectos (sólo en flop/turn): qué celdas tienen algún combo vivo del rango con el proyecto
+            let board: Vec<MyCard> = self.board.iter().flatten().copied().collect();
+            let known = self.get_all_known_cards();
+            let draw_filter = self.draw_filter.filter(|_| (3..=4).contains(&board.len()));
+            let has_draw = draw_filter.map(|kind| {
+                self.sync_board_draws(&board);
+                let (board_draws, range) = (self.board_draws.as_ref().expect("recién clasificados"), self.matrix_range());
+                let mut cells = [[false; 13]; 13];
+                for (r1_idx, row) in cells.iter_mut().enumerate() {
+                    for (r2_idx, cell) in row.iter_mut().enumerate() {
+                        *cell = cell_combos(r1_idx, r2_idx)
+                            .iter()
+                            .any(|c| range.combo_weight(c) > 0.0 && !known.intersects(CardSet::from(c)) && board_draws.get(c).has(kind));
+                    }
                 }
-                ui.end_row();
+                cells
+            });
 
-                for (r1_idx, r1) in ranks.iter().enumerate() {
-                    // Row Header
-                    ui.label(egui::RichText::new(rank_to_str(*r1)).strong().size(16.0));
+            // Matriz y, al lado, el reparto del rango por mano hecha
+            ui.horizontal_top(|ui| {
+                egui::Grid::new("poker_matrix").spacing([2.0, 2.0]).show(ui, |ui| {
+                    // Header (Ranks)
+                    ui.label(""); // Esquina vacía
+                    for r in RANKS {
+                        ui.label(egui::RichText::new(rank_to_str(r)).strong().size(16.0));
+                    }
+                    ui.end_row();
 
-                    for (r2_idx, r2) in ranks.iter().enumerate() {
-                        let is_pair = r1_idx == r2_idx;
-                        let is_suited = r1_idx < r2_idx; // Upper triangle
-                        
-                        // Nombre de la celda (ej: AKs, 77, QJo)
-                        let (rank_a, rank_b) = if r1_idx <= r2_idx { (r1, r2) } else { (r2, r1) };
-                        let suffix = if is_pair { "" } else if is_suited { "s" } else { "o" };
-                        let cell_name = format!("{}{}{}", 
-                            rank_to_str(*rank_a), 
-                            rank_to_str(*rank_b), 
-                            suffix
-                        );
-
-                        // --- LÓGICA DE COLOR Y ESTADO ---
-                        let mut blocked_count = 0;
-                        let mut winning_count = 0;
-                        let mut losing_count = 0;
-                        let mut tie_count = 0;
-                        let mut total_combos = 0;
-
-                        // Generar los combos reales para esta celda
-                        let suits = [Suit::Clubs, Suit::Diamonds, Suit::Hearts, Suit::Spades];
-                        for s1 in suits {
-                            for s2 in suits {
-                                // Filtrar combos inválidos para la celda
-                                if is_pair && s1 == s2 { continue; } // AA no puede ser AhAh
-                                if is_suited && s1 != s2 { continue; }
-                                if !is_suited && !is_pair && s1 == s2 { continue; }
-                                
-                                // Para offsuit, evitar duplicados inversos. La matriz itera r1, r2.
-                                // La convención visual es r1 filas, r2 columnas.
-                                // Si r1 < r2 (suited area), la mano es r1-r2 suited.
-                                // Si r1 > r2 (offsuit area), la mano es r1-r2 offsuit.
-                                
-                                // Validar que las cartas coincidan con la definición de la celda
-                                let c1 = MyCard { rank: *r1, suit: s1 };
-                                let c2 = MyCard { rank: *r2, suit: s2 };
-
-                                // Chequear bloqueos (Hero, Aliados, Board)
-                                if known_cards.contains(&c1) || known_cards.contains(&c2) {
-                                    blocked_count += 1;
-                                    total_combos += 1;
-                                    continue;
-                                }
+                    for (r1_idx, r1) in RANKS.iter().enumerate() {
+                        // Row Header
+                        ui.label(egui::RichText::new(rank_to_str(*r1)).strong().size(16.0));
 
-                                total_combos += 1;
-
-                                // Si llegamos aquí, el combo es posible para el villano
-                                if let Some(h_score) = hero_score {
-                                    let mut villain_hand = vec![c1.to_poker_card(), c2.to_poker_card()];
-                                    villain_hand.extend(board_cards.clone());
-                                    
-                                    // Evaluar Villano
-                                    if let Ok(v_score) = self.evaluator.evaluate(&villain_hand) {
-                                        if v_score > h_score {
-                                            losing_count += 1; // Villano Mayor -> Yo Pierdo
-                                        } else if v_score < h_score {
-                                            winning_count += 1; // Villano Menor -> Yo Gano
-                                        } else {
-                                            tie_count += 1;
-                                        }
-                                    }
-                                }
-                            }
-                        }
+                        for r2_idx in 0..RANKS.len() {
+                            let is_pair = r1_idx == r2_idx;
+                            let is_suited = r1_idx < r2_idx; // Upper triangle
 
-                        // Determinar color de la celda
-                        let is_fully_blocked = blocked_count == total_combos && total_combos > 0;
-                        let user_excluded = self.excluded_cells.contains(&(r1_idx, r2_idx));
-                        
-                        let bg_color = if user_excluded {
-                            egui::Color32::from_rgb(20, 20, 20) // Casi negro (desactivado manual)
-                        } else if is_fully_blocked {
-                            egui::Color32::from_rgb(40, 40, 40) // Gris oscuro (bloqueado por cartas)
-                        } else if losing_count > 0 {
-                             egui::Color32::from_rgb(200, 50, 50) // ROJO FUERTE: Peligro
-                        } else if winning_count > 0 {
-                             egui::Color32::from_rgb(50, 180, 50) // VERDE FUERTE: Ganas
-                        } else if tie_count > 0 {
-                             egui::Color32::from_rgb(50, 100, 200) // AZUL: Empate
-                        } else {
-                            // Estado neutro
-                            if hero_score.is_some() {
-                                // Si estamos evaluando y no hay ganador ni perdedor (y no está bloqueado totalmente),
-                                // significa que no hay combos válidos para esa celda en específico (ej: 22 está en board).
-                                egui::Color32::from_rgb(30, 30, 30)
-                            } else {
-                                // Pre-flop / Inactivo
-                                if is_pair { egui::Color32::from_rgb(100, 80, 0) } // Marrón
-                                else if is_suited { egui::Color32::from_rgb(0, 60, 60) } // Cyan
-                                else { egui::Color32::from_rgb(50, 50, 50) } // Gris
-                            }
-                        };
+                            // Nombre de la celda (ej: AKs, 77, QJo)
+                            let cell_name = cell_name(r1_idx, r2_idx);
 
-                        let text_color = if is_fully_blocked || user_excluded {
-                            egui::Color32::GRAY
-                        } else {
-                            egui::Color32::WHITE
-                        };
+                            // --- LÓGICA DE COLOR Y ESTADO ---
+                            let cell = report.cells[r1_idx][r2_idx];
+                            let winning_count = cell.ahead;
+                            let losing_count = cell.behind;
+                            let tie_count = cell.even;
 
-                        let btn = egui::Button::new(egui::RichText::new(cell_name).size(12.0).color(text_color))
-                            .fill(bg_color)
-                            .min_size(egui::vec2(35.0, 35.0));
+                            // Determinar color de la celda
+                            let is_fully_blocked = cell.is_fully_blocked();
+                            let weight = self.matrix_range().cell_weight(r1_idx, r2_idx);
+                            let user_excluded = weight <= 0.0;
+                            let filtered_out = has_draw.is_some_and(|cells| !cells[r1_idx][r2_idx]);
 
-                        if ui.add(btn).clicked() {
-                            if user_excluded {
-                                self.excluded_cells.remove(&(r1_idx, r2_idx));
+                            let bg_color = if user_excluded {
+                                egui::Color32::from_rgb(20, 20, 20) // Casi negro (desactivado manual)
+                            } else if is_fully_blocked {
+                                egui::Color32::from_rgb(40, 40, 40) // Gris oscuro (bloqueado por cartas)
+                            } else if filtered_out {
+                                egui::Color32::from_rgb(30, 30, 30) // Sin el proyecto elegido
+                            } else if self.gradient_colors && cell.outcome.total() > 0.0 {
+                                equity_color(cell.outcome.equity()) // GRADIENTE: equity ponderada de la celda
+                            } else if losing_count > 0 {
+                                 egui::Color32::from_rgb(200, 50, 50) // ROJO FUERTE: Peligro
+                            } else if winning_count > 0 {
+                                 egui::Color32::from_rgb(50, 180, 50) // VERDE FUERTE: Ganas
+                            } else if tie_count > 0 {
+                                 egui::Color32::from_rgb(50, 100, 200) // AZUL: Empate
                             } else {
-                                self.excluded_cells.insert((r1_idx, r2_idx));
+                                // Estado neutro
+                                if hero_ready {
+                                    // Si estamos evaluando y no hay ganador ni perdedor (y no está bloqueado totalmente),
+                                    // significa que no hay combos válidos para esa celda en específico (ej: 22 está en board).
+                                    egui::Color32::from_rgb(30, 30, 30)
+                                } else {
+                                    // Pre-flop / Inactivo
+                                    if is_pair { egui::Color32::from_rgb(100, 80, 0) } // Marrón
+                                    else if is_suited { egui::Color32::from_rgb(0, 60, 60) } // Cyan
+                                    else { egui::Color32::from_rgb(50, 50, 50) } // Gris
+                                }
+                            };
+
+                            let text_color = if is_fully_blocked || user_excluded || filtered_out {
+                                egui::Color32::GRAY
+                            } else {
+                                egui::Color32::WHITE
+                            };
+
+                            // Excluida: la pintamos entera (negro); si no, relleno parcial según el peso
+                            let fill_weight = if user_excluded { 1.0 } else { weight };
+                            let response = matrix_cell(ui, &cell_name, bg_color, text_color, fill_weight)
+                                .on_hover_ui(|ui| self.render_cell_detail(ui, &report, r1_idx, r2_idx));
+                            if response.clicked() {
+                                // Click con el mismo peso que ya tiene = excluir
+                                let new_weight = if weight == self.brush_weight { 0.0 } else { self.brush_weight };
+                                self.matrix_range_mut().set_cell_weight(r1_idx, r2_idx, new_weight);
+                            }
+                            if response.secondary_clicked() {
+                                self.editing_cell = Some((r1_idx, r2_idx));
                             }
                         }
+                        ui.end_row();
                     }
-                    ui.end_row();
-                }
+                });
+                ui.separator();
+                self.render_breakdown(ui);
             });
             
             ui.add_space(15.0);
@@ -555,28 +1396,46 @@ impl eframe::App for PokerApp {
             ui.label(egui::RichText::new("📖 AYUDA MEMORIA DE COLORES").strong().size(14.0));
             
             egui::Grid::new("legend_grid").spacing([20.0, 5.0]).show(ui, |ui| {
-                ui.label(egui::RichText::new("🟥 ROJO").color(egui::Color32::from_rgb(200, 50, 50)).strong());
-                ui.label("Peligro: El villano tiene al menos una combinación en esta celda que te gana.");
-                ui.end_row();
+                if self.gradient_colors {
+                    ui.label(egui::RichText::new("🟥→🟨→🟩 GRADIENTE").color(equity_color(0.75)).strong());
+                    ui.label("Tu equity contra la celda (victorias + mitad de empates, ponderado por el rango): rojo 0%, ámbar 50%, verde 100%.");
+                    ui.end_row();
+                } else {
+                    ui.label(egui::RichText::new("🟥 ROJO").color(egui::Color32::from_rgb(200, 50, 50)).strong());
+                    ui.label("Peligro: El villano tiene al menos una combinación en esta celda que es favorita contra tu mano.");
+                    ui.end_row();
 
-                ui.label(egui::RichText::new("🟩 VERDE").color(egui::Color32::from_rgb(50, 180, 50)).strong());
-                ui.label("Seguro: Tu mano actual vence a todas las combinaciones posibles de esta celda.");
-                ui.end_row();
+                    ui.label(egui::RichText::new("🟩 VERDE").color(egui::Color32::from_rgb(50, 180, 50)).strong());
+                    ui.label("Seguro: Tu mano es favorita contra todas las combinaciones posibles de esta celda.");
+                    ui.end_row();
 
-                ui.label(egui::RichText::new("🟦 AZUL").color(egui::Color32::from_rgb(50, 100, 200)).strong());
-                ui.label("Empate: Tu mano y la del villano tienen el mismo valor (Split Pot).");
-                ui.end_row();
+                    ui.label(egui::RichText::new("🟦 AZUL").color(egui::Color32::from_rgb(50, 100, 200)).strong());
+                    ui.label("Empate: Tu mano y la del villano tienen la misma equity (Split Pot).");
+                    ui.end_row();
+                }
 
                 ui.label(egui::RichText::new("⬛ GRIS OSCURO").color(egui::Color32::GRAY).strong());
                 ui.label("Bloqueado: Esta mano es imposible porque las cartas están en tu mano, aliados o mesa.");
                 ui.end_row();
 
                 ui.label(egui::RichText::new("🔘 NEGRO").color(egui::Color32::BLACK).strong());
-                ui.label("Excluido: Hiciste click para quitar esta mano del rango del villano manualmente.");
+                ui.label("Excluido: Hiciste click para quitar esta mano del rango del villano. No cuenta en las estadísticas.");
+                ui.end_row();
+
+                ui.label(egui::RichText::new("▄ RELLENO PARCIAL").strong());
+                ui.label("Frecuencia: la celda se pinta según el peso de la mano en el rango (ej: AKo al 50%). Las estadísticas se ponderan igual.");
+                ui.end_row();
+
+                ui.label(egui::RichText::new("🔎 PROYECTOS").strong());
+                ui.label("En flop/turn, el filtro de proyectos deja en color sólo las celdas con algún combo (vivo y en rango) con ese proyecto.");
+                ui.end_row();
+
+                ui.label(egui::RichText::new("🖱 CLICK DERECHO").strong());
+                ui.label("Abre el editor de combos de la celda: incluir, excluir o ponderar cada combinación de palos.");
                 ui.end_row();
 
                 ui.label(egui::RichText::new("🟫 MARRÓN/CYAN").color(egui::Color32::from_rgb(100, 80, 0)).strong());
-                ui.label("Pre-flop: Indica Pares (Marrón) y Suited (Cyan). Se activan los colores reales al poner el Flop.");
+                ui.label("Pre-flop: Indica Pares (Marrón) y Suited (Cyan). Se activan los colores reales al elegir tus 2 cartas (preflop incluido).");
                 ui.end_row();
             });
         });
@@ -0,0 +1,100 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::{EquityEngine, Outcome, Spot};
+use crate::evaluator::{HandCategory, HandRank};
+
+// --- OUTS DEL HERO ---
+//
+// En flop y turn: cartas no vistas que hacen pasar al hero de ir por detrás a ir por delante
+// si se mostraran las manos con la carta siguiente (contra la mano del villano o su rango).
+// Una out está "manchada" si a la vez mejora algún combo del villano hasta ganarle al hero.
+
+#[derive(Clone, Copy, Debug)]
+pub struct Out {
+    pub card: MyCard,
+    pub category: HandCategory, // Mano que hace el hero con la carta
+    pub equity: f64,            // Equity del hero al mostrar las manos con la carta
+    pub tainted: f64,           // Parte del rango (ponderada) que la carta mejora y queda por delante del hero
+}
+
+impl Out {
+    pub fn is_clean(&self) -> bool {
+        self.tainted == 0.0
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OutsReport {
+    pub equity: f64,    // Equity del hero si se mostraran las manos ahora
+    pub unseen: usize,  // Cartas que pueden salir (sin las muertas ni las de los aliados)
+    pub outs: Vec<Out>, // Vacío si el hero ya va por delante
+}
+
+impl OutsReport {
+    pub fn is_behind(&self) -> bool {
+        self.equity < 0.5
+    }
+
+    pub fn clean_count(&self) -> usize {
+        self.outs.iter().filter(|o| o.is_clean()).count()
+    }
+
+    // Probabilidad de ligar una out en la próxima carta
+    pub fn hit_chance(&self) -> f64 {
+        if self.unseen == 0 { 0.0 } else { self.outs.len() as f64 / self.unseen as f64 }
+    }
+}
+
+impl EquityEngine {
+    // Outs del hero en flop o turn contra el villano (o su rango). None si no hay mano o mesa para calcularlas.
+    // Los rivales extra (multiway) no cuentan.
+    pub fn outs(&self, spot: &Spot) -> Option<OutsReport> {
+        let hero: [MyCard; 2] = spot.hero.as_slice().try_into().ok()?;
+        if !(3..=4).contains(&spot.board.len()) {
+            return None;
+        }
+        let board: CardSet = spot.board.iter().copied().collect();
+        let villains = match spot.villain.as_slice() {
+            [v1, v2] => vec![([*v1, *v2], 1.0)],
+            _ => spot.live_range_combos(),
+        };
+
+        let (now, _) = self.showdown_now(&hero, board, &villains);
+        let unseen: Vec<MyCard> = spot.dead.complement().iter().collect();
+        let mut report = OutsReport { equity: now.equity(), unseen: unseen.len(), outs: Vec::new() };
+        if !report.is_behind() {
+            return Some(report);
+        }
+
+        for card in unseen {
+            let mut next = board;
+            next.insert(card);
+            let live: Vec<([MyCard; 2], f64)> = villains.iter().copied().filter(|(c, _)| !CardSet::from(c).contains(card)).collect();
+            let (after, hero_rank) = self.showdown_now(&hero, next, &live);
+            if after.equity() <= 0.5 {
+                continue;
+            }
+            // Combos del villano que la carta sube de categoría y que quedan por delante del hero
+            let total: f64 = live.iter().map(|(_, w)| w).sum();
+            let improved: f64 = live
+                .iter()
+                .filter(|(c, _)| {
+                    let villain_rank = self.rank(next.union(CardSet::from(c)));
+                    villain_rank > hero_rank && villain_rank.category() > self.rank(board.union(CardSet::from(c))).category()
+                })
+                .map(|(_, w)| w)
+                .sum();
+            report.outs.push(Out { card, category: hero_rank.category(), equity: after.equity(), tainted: if total > 0.0 { improved / total } else { 0.0 } });
+        }
+        Some(report)
+    }
+
+    // Resultado del hero si se mostraran las manos con esta mesa, y el valor de su mano
+    fn showdown_now(&self, hero: &[MyCard; 2], board: CardSet, villains: &[([MyCard; 2], f64)]) -> (Outcome, HandRank) {
+        let hero_rank = self.rank(board.union(CardSet::from(hero)));
+        let mut outcome = Outcome::default();
+        for (combo, weight) in villains {
+            outcome.record_weighted(hero_rank, self.rank(board.union(CardSet::from(combo))), *weight);
+        }
+        (outcome, hero_rank)
+    }
+}
@@ -0,0 +1,52 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// --- REPARTO DE TRABAJO ENTRE HILOS ---
+
+// Cantidad de hilos por defecto: todos los núcleos disponibles
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+}
+
+// Calcula `f(0..n)` en `threads` hilos y devuelve los resultados en orden de índice.
+// Cada hilo toma el siguiente índice libre, así que qué hilo calcula qué no importa:
+// combinar los resultados en orden da siempre lo mismo, con 1 hilo o con 16.
+pub fn map_indexed<R: Send>(n: usize, threads: usize, f: impl Fn(usize) -> R + Sync) -> Vec<R> {
+    let threads = threads.clamp(1, n.max(1));
+    if threads == 1 {
+        return (0..n).map(f).collect();
+    }
+
+    let next = AtomicUsize::new(0);
+    let mut slots: Vec<Option<R>> = (0..n).map(|_| None).collect();
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        if i >= n {
+                            break;
+                        }
+                        done.push((i, f(i)));
+                    }
+                    done
+                })
+            })
+            .collect();
+        for handle in handles {
+            for (i, result) in handle.join().expect("hilo de cálculo") {
+                slots[i] = Some(result);
+            }
+        }
+    });
+    slots.into_iter().map(|r| r.expect("todos los índices calculados")).collect()
+}
+
+// Parte `items` en bloques fijos de `chunk` elementos (no dependen de la cantidad de hilos)
+// y aplica `f` a cada bloque en paralelo. Los resultados vuelven en orden de bloque.
+pub fn map_chunks<T: Sync, R: Send>(items: &[T], chunk: usize, threads: usize, f: impl Fn(&[T]) -> R + Sync) -> Vec<R> {
+    let chunks: Vec<&[T]> = items.chunks(chunk.max(1)).collect();
+    map_indexed(chunks.len(), threads, |i| f(chunks[i]))
+}
@@ -0,0 +1,65 @@
+use crate::card::{combo_cell, combo_key, CardSet, MyCard};
+use crate::equity::Outcome;
+use crate::iso::SuitPermutation;
+
+// --- TABLA PREFLOP ---
+//
+// Antes del flop cada duelo tiene C(48,5) mesas: recorrerlas contra un rango entero es demasiado lento.
+// La tabla guarda, para una mano de cada celda y cada combo rival, en cuántas de esas mesas gana el hero
+// y en cuántas empata. Las demás manos de la celda se llevan a la guardada renombrando los palos.
+// Se regenera con `cargo run --release --bin preflop_table` (data/preflop_table.bin).
+
+// Mesas de un duelo preflop sin más cartas muertas: C(48, 5)
+pub const PREFLOP_DUEL_BOARDS: u32 = 1_712_304;
+// Combos de dos cartas: C(52, 2)
+pub const COMBOS: usize = 1_326;
+
+static TABLE: &[u8] = include_bytes!("../data/preflop_table.bin");
+
+// Posición del combo dentro de una fila de la tabla (0..COMBOS)
+pub fn combo_index(combo: &[MyCard; 2]) -> usize {
+    let (a, b) = combo_key(combo);
+    b * (b - 1) / 2 + a
+}
+
+// Mano guardada para la celda del combo (la de menor clave entre las 24 formas de renombrar los palos)
+// y la permutación que lleva el combo a ella
+pub fn representative(combo: &[MyCard; 2]) -> (SuitPermutation, [MyCard; 2]) {
+    SuitPermutation::all()
+        .into_iter()
+        .map(|perm| (perm, perm.apply_combo(combo)))
+        .min_by_key(|(_, c)| combo_key(c))
+        .expect("hay 24 permutaciones")
+}
+
+// Fila de la tabla de un hero: se busca una vez y se consulta por cada combo rival
+pub(crate) struct PreflopRow {
+    perm: SuitPermutation,
+    offset: usize,
+    hero: CardSet,
+}
+
+impl PreflopRow {
+    pub(crate) fn new(hero: &[MyCard; 2]) -> Self {
+        let (perm, stored) = representative(hero);
+        let (r1_idx, r2_idx) = combo_cell(&stored);
+        Self { perm, offset: (r1_idx * 13 + r2_idx) * COMBOS, hero: CardSet::from(hero) }
+    }
+
+    // Resultado exacto del hero contra el combo (normalizado a una mano). None si comparten carta.
+    pub(crate) fn outcome(&self, villain: &[MyCard; 2]) -> Option<Outcome> {
+        if self.hero.intersects(CardSet::from(villain)) {
+            return None;
+        }
+        let entry = (self.offset + combo_index(&self.perm.apply_combo(villain))) * 8;
+        let count = |at: usize| u32::from_le_bytes(TABLE[at..at + 4].try_into().expect("4 bytes")) as f64;
+        let (wins, ties) = (count(entry), count(entry + 4));
+        let boards = PREFLOP_DUEL_BOARDS as f64;
+        Some(Outcome { wins: wins / boards, losses: (boards - wins - ties) / boards, ties: ties / boards, tie_share: ties / boards / 2.0 })
+    }
+}
+
+// Resultado exacto de `hero` contra `villain` antes del flop, sin más cartas muertas. None si comparten carta.
+pub fn preflop_outcome(hero: &[MyCard; 2], villain: &[MyCard; 2]) -> Option<Outcome> {
+    PreflopRow::new(hero).outcome(villain)
+}
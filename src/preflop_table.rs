@@ -0,0 +1,91 @@
+// Genera data/preflop_table.bin: victorias y empates de una mano por celda contra cada combo rival,
+// recorriendo todas las mesas preflop. Cada mesa se calcula una sola vez por clase de palos (las 24
+// formas de renombrarlos) y los conteos se reparten después sobre las permutaciones.
+
+use poker_solver::preflop::{combo_index, representative, COMBOS, PREFLOP_DUEL_BOARDS};
+use poker_solver::{cell_combos, CardSet, HandEvaluator, MyCard, SuitPermutation};
+use std::cmp::Ordering;
+
+fn main() {
+    let evaluator = HandEvaluator::new();
+    let perms = SuitPermutation::all();
+    let mut combos = vec![[MyCard::from_index(0); 2]; COMBOS];
+    for b in 1..52 {
+        for a in 0..b {
+            let combo = [MyCard::from_index(a), MyCard::from_index(b)];
+            combos[combo_index(&combo)] = combo;
+        }
+    }
+    let sets: Vec<CardSet> = combos.iter().map(CardSet::from).collect();
+
+    // Por par (i < j): mesas donde gana i, donde gana j y empates, cada mesa canónica pesando su órbita
+    let mut above = vec![0u32; COMBOS * COMBOS];
+    let mut below = vec![0u32; COMBOS * COMBOS];
+    let mut even = vec![0u32; COMBOS * COMBOS];
+    let mut live: Vec<(usize, CardSet, _)> = Vec::with_capacity(COMBOS);
+    let mut canonical_boards = 0;
+
+    let mut cards = [0, 1, 2, 3, 4];
+    loop {
+        // La mesa se calcula sólo si es la menor de su órbita
+        let mask = |perm: &SuitPermutation| cards.iter().fold(0u64, |m, &c| m | 1 << perm.apply_index(c));
+        let own = mask(&perms[0]);
+        let images: Vec<u64> = perms.iter().map(mask).collect();
+        if images.iter().all(|&m| m >= own) {
+            let weight = 24 / images.iter().filter(|&&m| m == own).count() as u32;
+            let board: CardSet = cards.iter().map(|&c| MyCard::from_index(c)).collect();
+            live.clear();
+            live.extend((0..COMBOS).filter(|&i| !sets[i].intersects(board)).map(|i| (i, sets[i], evaluator.evaluate(board.union(sets[i])))));
+            for (n, &(i, set_i, rank_i)) in live.iter().enumerate() {
+                let row = i * COMBOS;
+                for &(j, set_j, rank_j) in &live[n + 1..] {
+                    if set_i.intersects(set_j) {
+                        continue;
+                    }
+                    match rank_i.cmp(&rank_j) {
+                        Ordering::Greater => above[row + j] += weight,
+                        Ordering::Less => below[row + j] += weight,
+                        Ordering::Equal => even[row + j] += weight,
+                    }
+                }
+            }
+            canonical_boards += 1;
+            if canonical_boards % 10_000 == 0 {
+                eprintln!("{} mesas canónicas", canonical_boards);
+            }
+        }
+        // Siguiente combinación de 5 cartas
+        let Some(k) = (0..5).rev().find(|&k| cards[k] < 52 - 5 + k) else { break };
+        cards[k] += 1;
+        for m in k + 1..5 {
+            cards[m] = cards[m - 1] + 1;
+        }
+    }
+
+    // Cada celda: su mano guardada contra cada combo, sumando sobre las 24 permutaciones
+    let mut table = Vec::with_capacity(169 * COMBOS * 8);
+    for r1_idx in 0..13 {
+        for r2_idx in 0..13 {
+            let (_, hero) = representative(&cell_combos(r1_idx, r2_idx)[0]);
+            for villain in &combos {
+                let (mut wins, mut ties, mut total) = (0u64, 0u64, 0u64);
+                if !CardSet::from(&hero).intersects(CardSet::from(villain)) {
+                    for perm in &perms {
+                        let (h, v) = (combo_index(&perm.apply_combo(&hero)), combo_index(&perm.apply_combo(villain)));
+                        let at = h.min(v) * COMBOS + h.max(v);
+                        wins += if h < v { above[at] } else { below[at] } as u64;
+                        ties += even[at] as u64;
+                        total += (above[at] + below[at] + even[at]) as u64;
+                    }
+                    assert_eq!(total, 24 * PREFLOP_DUEL_BOARDS as u64, "todas las mesas del duelo");
+                    assert!(wins % 24 == 0 && ties % 24 == 0);
+                }
+                table.extend_from_slice(&((wins / 24) as u32).to_le_bytes());
+                table.extend_from_slice(&((ties / 24) as u32).to_le_bytes());
+            }
+        }
+    }
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/data/preflop_table.bin");
+    std::fs::write(path, table).expect("no se pudo escribir la tabla");
+    eprintln!("{} mesas canónicas, tabla escrita en {}", canonical_boards, path);
+}
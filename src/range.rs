@@ -0,0 +1,355 @@
+use crate::card::{cell_combos, combo_key, MyCard, RANKS, SUITS};
+use crate::iso::SuitPermutation;
+use std::cmp::Ordering;
+use std::fmt;
+use std::str::FromStr;
+
+// --- RANGO DEL VILLANO ---
+
+// Rango definido por la matriz 13x13. Las celdas se indexan como en la grilla: (fila, columna).
+// Cada combo tiene un peso 0.0..=1.0 (frecuencia con la que el villano juega esa mano);
+// el peso de una celda es el promedio de sus combos.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Range {
+    weights: Vec<f32>, // Indexado por combo_key: carta_menor * 52 + carta_mayor
+}
+
+impl Default for Range {
+    fn default() -> Self {
+        Self::full()
+    }
+}
+
+fn weight_idx(combo: &[MyCard; 2]) -> usize {
+    let (a, b) = combo_key(combo);
+    a * 52 + b
+}
+
+impl Range {
+    // Rango completo: cualquier mano posible al 100%
+    pub fn full() -> Self {
+        // Sólo los combos válidos (carta menor < mayor): así es igual al rango leído de "22+, A2+, ..."
+        let mut range = Self::empty();
+        for r1_idx in 0..13 {
+            for r2_idx in 0..13 {
+                range.set_cell_weight(r1_idx, r2_idx, 1.0);
+            }
+        }
+        range
+    }
+
+    // Rango vacío: ninguna mano
+    pub fn empty() -> Self {
+        Self { weights: vec![0.0; 52 * 52] }
+    }
+
+    pub fn combo_weight(&self, combo: &[MyCard; 2]) -> f32 {
+        self.weights[weight_idx(combo)]
+    }
+
+    pub fn set_combo_weight(&mut self, combo: &[MyCard; 2], weight: f32) {
+        self.weights[weight_idx(combo)] = weight.clamp(0.0, 1.0);
+    }
+
+    pub fn cell_weight(&self, r1_idx: usize, r2_idx: usize) -> f32 {
+        let combos = cell_combos(r1_idx, r2_idx);
+        combos.iter().map(|c| self.combo_weight(c)).sum::<f32>() / combos.len() as f32
+    }
+
+    pub fn set_cell_weight(&mut self, r1_idx: usize, r2_idx: usize, weight: f32) {
+        for combo in cell_combos(r1_idx, r2_idx) {
+            self.set_combo_weight(&combo, weight);
+        }
+    }
+
+    // Mismo rango con los palos cambiados
+    pub fn permuted_suits(&self, perm: SuitPermutation) -> Range {
+        if perm.is_identity() {
+            return self.clone();
+        }
+        let mut permuted = Range::empty();
+        for a in 0..52 {
+            for b in a + 1..52 {
+                let (pa, pb) = (perm.apply_index(a), perm.apply_index(b));
+                permuted.weights[pa.min(pb) * 52 + pa.max(pb)] = self.weights[a * 52 + b];
+            }
+        }
+        permuted
+    }
+
+    // Orden total entre rangos (peso a peso), para elegir una forma canónica
+    pub fn compare_weights(&self, other: &Range) -> Ordering {
+        self.weights.iter().zip(&other.weights).map(|(a, b)| a.total_cmp(b)).find(|o| o.is_ne()).unwrap_or(Ordering::Equal)
+    }
+
+    // Peso común de todos los combos de la celda, o None si están mezclados
+    fn uniform_cell_weight(&self, r1_idx: usize, r2_idx: usize) -> Option<f32> {
+        let combos = cell_combos(r1_idx, r2_idx);
+        let first = self.combo_weight(&combos[0]);
+        combos.iter().all(|c| self.combo_weight(c) == first).then_some(first)
+    }
+
+    // Todos los combos del rango con su peso (sin filtrar bloqueos)
+    pub fn weighted_combos(&self) -> Vec<([MyCard; 2], f64)> {
+        let mut combos = Vec::new();
+        for r1_idx in 0..13 {
+            for r2_idx in 0..13 {
+                for combo in cell_combos(r1_idx, r2_idx) {
+                    let weight = self.combo_weight(&combo) as f64;
+                    if weight > 0.0 {
+                        combos.push((combo, weight));
+                    }
+                }
+            }
+        }
+        combos
+    }
+}
+
+// --- NOTACIÓN DE RANGOS ---
+// Ej: "22+, A2s+, KTo+, QJs, 55-77, A5s-A2s, AhKh, AKo:0.5"
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+    Pair,
+    Suited,
+    Offsuit,
+    Any, // "AK" = suited + offsuit
+}
+
+// Clase de mano: índices en RANKS (0 = As), `high` <= `low`
+#[derive(Clone, Copy)]
+struct HandClass {
+    high: usize,
+    low: usize,
+    kind: Kind,
+}
+
+impl HandClass {
+    fn cells(&self) -> Vec<(usize, usize)> {
+        match self.kind {
+            Kind::Pair => vec![(self.high, self.high)],
+            Kind::Suited => vec![(self.high, self.low)],
+            Kind::Offsuit => vec![(self.low, self.high)],
+            Kind::Any => vec![(self.high, self.low), (self.low, self.high)],
+        }
+    }
+}
+
+fn parse_rank(c: char) -> Option<usize> {
+    RANKS.iter().position(|r| MyCard::new(*r, SUITS[0]).rank_char() == c.to_ascii_uppercase())
+}
+
+fn parse_card(rank: char, suit: char) -> Option<MyCard> {
+    let rank = RANKS[parse_rank(rank)?];
+    let suit = SUITS.into_iter().find(|s| MyCard::new(rank, *s).suit_char() == suit.to_ascii_lowercase())?;
+    Some(MyCard::new(rank, suit))
+}
+
+fn parse_class(text: &str) -> Result<HandClass, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let bad = || format!("Mano inválida: '{}'", text);
+    if chars.len() < 2 || chars.len() > 3 {
+        return Err(bad());
+    }
+    let a = parse_rank(chars[0]).ok_or_else(bad)?;
+    let b = parse_rank(chars[1]).ok_or_else(bad)?;
+    let kind = match (a == b, chars.get(2).map(|c| c.to_ascii_lowercase())) {
+        (true, None) => Kind::Pair,
+        (false, None) => Kind::Any,
+        (false, Some('s')) => Kind::Suited,
+        (false, Some('o')) => Kind::Offsuit,
+        _ => return Err(bad()),
+    };
+    Ok(HandClass { high: a.min(b), low: a.max(b), kind })
+}
+
+// Expande un token sin peso a las celdas que cubre. Los combos concretos (AhKh) van aparte.
+fn expand(token: &str) -> Result<Vec<(usize, usize)>, String> {
+    let mut classes = Vec::new();
+    if let Some((from, to)) = token.split_once('-') {
+        // Rango con guión: "22-55", "A2s-A5s" o conectores con el mismo hueco "T9s-54s"
+        let (a, b) = (parse_class(from.trim())?, parse_class(to.trim())?);
+        let gap = a.low - a.high;
+        let connectors = a.kind != Kind::Pair && a.high != b.high && b.low - b.high == gap;
+        if a.kind != b.kind || (a.kind != Kind::Pair && a.high != b.high && !connectors) {
+            return Err(format!("Rango inválido: '{}'", token));
+        }
+        if connectors {
+            for high in a.high.min(b.high)..=a.high.max(b.high) {
+                classes.push(HandClass { high, low: high + gap, kind: a.kind });
+            }
+        } else {
+            let (top, bottom) = if a.kind == Kind::Pair { (a.high, b.high) } else { (a.low, b.low) };
+            for idx in top.min(bottom)..=top.max(bottom) {
+                classes.push(match a.kind {
+                    Kind::Pair => HandClass { high: idx, low: idx, kind: Kind::Pair },
+                    kind => HandClass { high: a.high, low: idx, kind },
+                });
+            }
+        }
+    } else if let Some(base) = token.strip_suffix('+') {
+        // Plus: "22+" sube el par hasta AA; "A2s+" sube el kicker hasta justo debajo de la carta alta
+        let class = parse_class(base)?;
+        if class.kind == Kind::Pair {
+            classes.extend((0..=class.high).map(|idx| HandClass { high: idx, low: idx, kind: Kind::Pair }));
+        } else {
+            classes.extend((class.high + 1..=class.low).map(|idx| HandClass { low: idx, ..class }));
+        }
+    } else {
+        classes.push(parse_class(token)?);
+    }
+    Ok(classes.iter().flat_map(|c| c.cells()).collect())
+}
+
+fn parse_weight(text: &str) -> Result<f32, String> {
+    let text = text.trim();
+    let (number, scale) = match text.strip_suffix('%') {
+        Some(pct) => (pct, 100.0),
+        None => (text, 1.0),
+    };
+    match number.trim().parse::<f32>() {
+        Ok(w) if (0.0..=scale).contains(&w) => Ok(w / scale),
+        _ => Err(format!("Peso inválido: '{}'", text)),
+    }
+}
+
+impl FromStr for Range {
+    type Err = String;
+
+    // Parte de un rango vacío y agrega cada token separado por comas
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let mut range = Range::empty();
+        for token in text.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            let (hand, weight) = match token.split_once(':') {
+                Some((hand, weight)) => (hand.trim(), parse_weight(weight)?),
+                None => (token, 1.0),
+            };
+
+            // Combo concreto: "AhKh"
+            let chars: Vec<char> = hand.chars().collect();
+            if chars.len() == 4 {
+                if let (Some(c1), Some(c2)) = (parse_card(chars[0], chars[1]), parse_card(chars[2], chars[3])) {
+                    if c1 == c2 {
+                        return Err(format!("Combo inválido: '{}'", hand));
+                    }
+                    range.set_combo_weight(&[c1, c2], weight);
+                    continue;
+                }
+            }
+
+            for (r1_idx, r2_idx) in expand(hand)? {
+                range.set_cell_weight(r1_idx, r2_idx, weight);
+            }
+        }
+        Ok(range)
+    }
+}
+
+fn rank_str(idx: usize) -> char {
+    MyCard::new(RANKS[idx], SUITS[0]).rank_char()
+}
+
+fn weight_suffix(weight: f32) -> String {
+    if weight >= 1.0 {
+        String::new()
+    } else {
+        format!(":{}", (weight * 1000.0).round() / 1000.0)
+    }
+}
+
+// Agrupa índices consecutivos (ya ordenados) en tramos
+fn runs(indices: &[usize]) -> Vec<(usize, usize)> {
+    let mut runs: Vec<(usize, usize)> = Vec::new();
+    for &idx in indices {
+        match runs.last_mut() {
+            Some((_, end)) if *end + 1 == idx => *end = idx,
+            _ => runs.push((idx, idx)),
+        }
+    }
+    runs
+}
+
+// Tokens de un tramo de kickers para una carta alta: "A2s+", "KTo-K8o", "QJ"
+fn kicker_tokens(high: usize, kickers: &[usize], suffix: &str, tokens: &mut Vec<String>) {
+    let (h, suf) = (rank_str(high), suffix);
+    for (top, bottom) in runs(kickers) {
+        if top == high + 1 && bottom > top {
+            tokens.push(format!("{}{}{}+", h, rank_str(bottom), suf));
+        } else if top == bottom {
+            tokens.push(format!("{}{}{}", h, rank_str(top), suf));
+        } else {
+            tokens.push(format!("{}{}{}-{}{}{}", h, rank_str(top), suf, h, rank_str(bottom), suf));
+        }
+    }
+}
+
+// Formato inverso: la notación más corta que encontramos para el rango actual
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Pesos distintos de las celdas uniformes, de mayor a menor
+        let mut group_weights: Vec<f32> = Vec::new();
+        for r1_idx in 0..13 {
+            for r2_idx in 0..13 {
+                if let Some(w) = self.uniform_cell_weight(r1_idx, r2_idx) {
+                    if w > 0.0 && !group_weights.contains(&w) {
+                        group_weights.push(w);
+                    }
+                }
+            }
+        }
+        group_weights.sort_by(|a, b| b.total_cmp(a));
+
+        let mut tokens = Vec::new();
+        for weight in group_weights {
+            let has = |r1_idx: usize, r2_idx: usize| self.uniform_cell_weight(r1_idx, r2_idx) == Some(weight);
+            let mut group = Vec::new();
+
+            // Pares
+            let pairs: Vec<usize> = (0..13).filter(|&i| has(i, i)).collect();
+            for (top, bottom) in runs(&pairs) {
+                let (t, b) = (rank_str(top), rank_str(bottom));
+                if top == 0 && bottom > 0 {
+                    group.push(format!("{}{}+", b, b));
+                } else if top == bottom {
+                    group.push(format!("{}{}", t, t));
+                } else {
+                    group.push(format!("{}{}-{}{}", t, t, b, b));
+                }
+            }
+
+            // Suited y offsuit por carta alta; si coinciden se escriben sin sufijo
+            for high in 0..13 {
+                let suited: Vec<usize> = (high + 1..13).filter(|&k| has(high, k)).collect();
+                let offsuit: Vec<usize> = (high + 1..13).filter(|&k| has(k, high)).collect();
+                let both: Vec<usize> = suited.iter().copied().filter(|k| offsuit.contains(k)).collect();
+                let only_suited: Vec<usize> = suited.iter().copied().filter(|k| !both.contains(k)).collect();
+                let only_offsuit: Vec<usize> = offsuit.iter().copied().filter(|k| !both.contains(k)).collect();
+                kicker_tokens(high, &both, "", &mut group);
+                kicker_tokens(high, &only_suited, "s", &mut group);
+                kicker_tokens(high, &only_offsuit, "o", &mut group);
+            }
+
+            let suffix = weight_suffix(weight);
+            tokens.extend(group.into_iter().map(|t| format!("{}{}", t, suffix)));
+        }
+
+        // Celdas con combos mezclados: combo por combo
+        for r1_idx in 0..13 {
+            for r2_idx in 0..13 {
+                if self.uniform_cell_weight(r1_idx, r2_idx).is_some() {
+                    continue;
+                }
+                for combo in cell_combos(r1_idx, r2_idx) {
+                    let weight = self.combo_weight(&combo);
+                    if weight > 0.0 {
+                        let [c1, c2] = combo;
+                        tokens.push(format!("{}{}{}{}{}", c1.rank_char(), c1.suit_char(), c2.rank_char(), c2.suit_char(), weight_suffix(weight)));
+                    }
+                }
+            }
+        }
+
+        write!(f, "{}", tokens.join(", "))
+    }
+}
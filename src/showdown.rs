@@ -0,0 +1,256 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::{block_rng, deal_hands, CalcMode, EquityEngine, Progress, MC_BLOCK, PREFLOP_SEED};
+use crate::evaluator::HandRank;
+use crate::parallel;
+use crate::range::Range;
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+
+// --- SHOWDOWN ENTRE VARIOS JUGADORES ---
+
+// Mano de un jugador en el showdown: cartas a la vista o un rango
+#[derive(Clone, Debug, PartialEq)]
+pub enum SeatHand {
+    Cards([MyCard; 2]),
+    Range(Range),
+}
+
+// Resultado de un jugador (0.0..=1.0)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct SeatResult {
+    pub win: f64,    // Gana el bote solo
+    pub tie: f64,    // Lo reparte con al menos otro jugador
+    pub equity: f64, // Parte del bote que se lleva en promedio
+}
+
+impl SeatResult {
+    fn add(&mut self, other: &SeatResult) {
+        self.win += other.win;
+        self.tie += other.tie;
+        self.equity += other.equity;
+    }
+
+    fn scaled(&self, factor: f64) -> SeatResult {
+        SeatResult { win: self.win * factor, tie: self.tie * factor, equity: self.equity * factor }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ShowdownReport {
+    pub seats: Vec<SeatResult>,
+    pub showdowns: f64, // Showdowns evaluados (ponderados por el peso de los combos)
+    pub sampled: bool,  // true = Monte Carlo (demasiadas combinaciones para recorrerlas todas)
+}
+
+// Jugadores que admite un showdown
+pub const MAX_SEATS: usize = 10;
+
+// Por encima de esta cantidad de evaluaciones el modo exhaustivo pasa a muestrear
+const MAX_EXHAUSTIVE_EVALS: f64 = 60_000_000.0;
+const SAMPLED_ITERATIONS: u32 = 200_000;
+// Mesas por tarea al repartir el trabajo entre hilos (fijo, para que el resultado no dependa de los hilos)
+const BOARD_CHUNK: usize = 4_096;
+
+impl EquityEngine {
+    // Equity de cada jugador con todas las manos a la vista (o por rango) recorriendo el resto de la mesa.
+    // `dead` = cartas fuera del mazo además de las de los jugadores y la mesa.
+    // None si se canceló o si hay más de MAX_SEATS jugadores.
+    pub fn showdown(&self, seats: &[SeatHand], board: &[MyCard], dead: CardSet, mode: CalcMode, progress: &Progress) -> Option<ShowdownReport> {
+        if seats.len() > MAX_SEATS {
+            return None;
+        }
+        let fixed = seats.iter().filter_map(|seat| match seat {
+            SeatHand::Cards(cards) => Some(CardSet::from(cards)),
+            SeatHand::Range(_) => None,
+        });
+        let known = fixed.fold(dead.union(board.iter().copied().collect()), CardSet::union);
+        // Combos posibles de cada jugador (las cartas a la vista son un rango de un solo combo)
+        let options: Vec<Vec<([MyCard; 2], f64)>> = seats
+            .iter()
+            .map(|seat| match seat {
+                SeatHand::Cards(cards) => vec![(*cards, 1.0)],
+                SeatHand::Range(range) => range.weighted_combos().into_iter().filter(|(c, _)| !known.intersects(CardSet::from(c))).collect(),
+            })
+            .collect();
+        let missing = 5 - board.len().min(5);
+        let ranged = seats.iter().filter(|s| matches!(s, SeatHand::Range(_))).count();
+        let deck_size = known.complement().len().saturating_sub(2 * ranged);
+
+        let assignments: f64 = options.iter().map(|o| o.len() as f64).product();
+        let evals = assignments * binomial(deck_size, missing) * seats.len() as f64;
+        let (iterations, seed) = match mode {
+            CalcMode::Exhaustive if evals <= MAX_EXHAUSTIVE_EVALS => return self.showdown_exhaustive(&options, board, known, missing, progress),
+            CalcMode::Exhaustive => (SAMPLED_ITERATIONS, PREFLOP_SEED),
+            CalcMode::MonteCarlo { iterations, seed } => (iterations, seed),
+        };
+        self.showdown_sampled(&options, board, known, missing, iterations, seed, progress)
+    }
+
+    fn showdown_exhaustive(&self, options: &[Vec<([MyCard; 2], f64)>], board: &[MyCard], known: CardSet, missing: usize, progress: &Progress) -> Option<ShowdownReport> {
+        let board_set: CardSet = board.iter().copied().collect();
+        // Las mesas se sacan del mazo sin las cartas fijas; las de cada reparto se filtran después
+        let deck: Vec<MyCard> = known.complement().iter().collect();
+        let mut completions = Vec::new();
+        boards(&deck, missing, CardSet::empty(), &mut completions);
+
+        // Los repartos no se guardan: cada tarea fija un combo del jugador con más combos y un tramo
+        // de mesas, y recorre ahí los repartos del resto (la memoria no crece con los rangos)
+        let split = (0..options.len()).max_by_key(|&seat| options[seat].len()).unwrap_or(0);
+        let chunks = completions.len().div_ceil(BOARD_CHUNK);
+        let tasks = options.get(split).map_or(0, Vec::len) * chunks;
+        progress.set_total(tasks);
+        let partials = parallel::map_indexed(tasks, self.threads(), |task| {
+            let mut results = vec![SeatResult::default(); options.len()];
+            let mut showdowns = 0.0;
+            if progress.is_cancelled() {
+                return (results, showdowns);
+            }
+            let pinned = options[split][task / chunks];
+            let start = (task % chunks) * BOARD_CHUNK;
+            let extras = &completions[start..(start + BOARD_CHUNK).min(completions.len())];
+            let seat_options = |seat: usize| if seat == split { std::slice::from_ref(&pinned) } else { options[seat].as_slice() };
+            assign(&seat_options, options.len(), &mut Vec::new(), CardSet::empty(), 1.0, &mut |hands, used, weight| {
+                for extra in extras {
+                    if extra.intersects(used) {
+                        continue;
+                    }
+                    self.settle(hands, board_set.union(*extra), weight, &mut results);
+                    showdowns += weight;
+                }
+            });
+            progress.advance(1);
+            (results, showdowns)
+        });
+        if progress.is_cancelled() {
+            return None;
+        }
+        Some(merge(options.len(), partials, false))
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn showdown_sampled(&self, options: &[Vec<([MyCard; 2], f64)>], board: &[MyCard], known: CardSet, missing: usize, iterations: u32, seed: u64, progress: &Progress) -> Option<ShowdownReport> {
+        let deck: Vec<MyCard> = known.complement().iter().collect();
+        let dists: Vec<Option<WeightedIndex<f64>>> = options.iter().map(|o| WeightedIndex::new(o.iter().map(|(_, w)| *w)).ok()).collect();
+        let to_deal = missing + 2 * options.len();
+
+        progress.set_total(iterations as usize);
+        let blocks = iterations.div_ceil(MC_BLOCK) as usize;
+        let partials = parallel::map_indexed(blocks, self.threads(), |block| {
+            let mut results = vec![SeatResult::default(); options.len()];
+            let mut showdowns = 0.0;
+            let mut rng = block_rng(seed, block);
+            let mut deck = deck.clone();
+            let start = block as u32 * MC_BLOCK;
+            let end = (start + MC_BLOCK).min(iterations);
+            if dists.iter().any(|d| d.is_none()) || deck.len() < to_deal {
+                return (results, showdowns);
+            }
+
+            for i in start..end {
+                if (i - start).is_multiple_of(1024) {
+                    if progress.is_cancelled() {
+                        break;
+                    }
+                    progress.advance(1024.min((end - i) as usize));
+                }
+                let Some((hands, used)) = deal_hands(options.len(), &mut rng, |seat, rng| {
+                    let dist = dists[seat].as_ref().expect("rangos no vacíos");
+                    options[seat][dist.sample(rng)].0
+                }) else {
+                    continue; // Ningún reparto sin choques en este intento
+                };
+                let (dealt, _) = deck.partial_shuffle(&mut rng, to_deal);
+                let full_board: CardSet = board.iter().chain(dealt.iter().filter(|c| !used.contains(**c)).take(missing)).copied().collect();
+                self.settle(&hands, full_board, 1.0, &mut results);
+                showdowns += 1.0;
+            }
+            (results, showdowns)
+        });
+        if progress.is_cancelled() {
+            return None;
+        }
+        Some(merge(options.len(), partials, true))
+    }
+
+    // Reparte el bote de una mesa completa entre las mejores manos
+    fn settle(&self, hands: &[[MyCard; 2]], board: CardSet, weight: f64, results: &mut [SeatResult]) {
+        let mut ranks = [HandRank::default(); MAX_SEATS];
+        for (rank, hand) in ranks.iter_mut().zip(hands) {
+            *rank = self.rank(board.union(CardSet::from(hand)));
+        }
+        let ranks = &ranks[..hands.len()];
+        let best = ranks.iter().max().copied().unwrap_or_default();
+        let winners = ranks.iter().filter(|r| **r == best).count();
+        for (result, rank) in results.iter_mut().zip(ranks) {
+            if *rank != best {
+                continue;
+            }
+            if winners == 1 {
+                result.win += weight;
+            } else {
+                result.tie += weight;
+            }
+            result.equity += weight / winners as f64;
+        }
+    }
+}
+
+// Recorre los repartos de combos sin cartas repetidas (asiento por asiento, `seat_options` da los
+// combos de cada uno) y llama a `visit` con las manos, sus cartas y su peso (producto de los pesos)
+fn assign<'a>(
+    seat_options: &impl Fn(usize) -> &'a [([MyCard; 2], f64)],
+    seats: usize,
+    hands: &mut Vec<[MyCard; 2]>,
+    used: CardSet,
+    weight: f64,
+    visit: &mut impl FnMut(&[[MyCard; 2]], CardSet, f64),
+) {
+    let seat = hands.len();
+    if seat == seats {
+        visit(hands, used, weight);
+        return;
+    }
+    for (combo, w) in seat_options(seat) {
+        let cards = CardSet::from(combo);
+        if used.intersects(cards) {
+            continue;
+        }
+        hands.push(*combo);
+        assign(seat_options, seats, hands, used.union(cards), weight * w, visit);
+        hands.pop();
+    }
+}
+
+// Todas las formas de completar la mesa con `k` cartas de `deck`
+fn boards(deck: &[MyCard], k: usize, current: CardSet, out: &mut Vec<CardSet>) {
+    if k == 0 {
+        out.push(current);
+        return;
+    }
+    for i in 0..deck.len().saturating_sub(k - 1) {
+        let mut next = current;
+        next.insert(deck[i]);
+        boards(&deck[i + 1..], k - 1, next, out);
+    }
+}
+
+// Suma los resultados parciales en orden y los pasa a fracciones
+fn merge(seats: usize, partials: Vec<(Vec<SeatResult>, f64)>, sampled: bool) -> ShowdownReport {
+    let mut totals = vec![SeatResult::default(); seats];
+    let mut showdowns = 0.0;
+    for (results, count) in partials {
+        for (total, result) in totals.iter_mut().zip(&results) {
+            total.add(result);
+        }
+        showdowns += count;
+    }
+    let factor = if showdowns > 0.0 { 1.0 / showdowns } else { 0.0 };
+    ShowdownReport { seats: totals.iter().map(|r| r.scaled(factor)).collect(), showdowns, sampled }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    if k > n {
+        return 0.0;
+    }
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
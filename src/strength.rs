@@ -0,0 +1,70 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::EquityEngine;
+use crate::evaluator::HandRank;
+use std::cmp::{Ordering, Reverse};
+
+// --- FUERZA RELATIVA DE LA MANO ---
+//
+// Dónde queda la mano del hero entre todas las manos de 2 cartas posibles con esta mesa:
+// su puesto entre los valores de mano distintos (1 = nuts) y a qué parte de los combos vivos gana.
+
+// Un valor de mano que le gana al hero, con los combos que lo tienen
+#[derive(Clone, Debug)]
+pub struct BetterHand {
+    pub rank: HandRank,
+    pub combos: Vec<[MyCard; 2]>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct HandStrength {
+    pub nut_rank: usize,         // 1 = nuts, 2 = segunda mejor mano posible...
+    pub live_combos: usize,      // Combos que no chocan con las cartas conocidas
+    pub beaten: usize,           // Combos a los que gana el hero
+    pub tied: usize,             // Combos con la misma mano
+    pub better: Vec<BetterHand>, // Manos que le ganan, de mejor a peor
+}
+
+impl HandStrength {
+    pub fn beats(&self) -> f64 {
+        if self.live_combos == 0 { 0.0 } else { self.beaten as f64 / self.live_combos as f64 }
+    }
+
+    pub fn is_nuts(&self) -> bool {
+        self.nut_rank == 1
+    }
+}
+
+impl EquityEngine {
+    // Fuerza de `hero` con la mesa actual (3 a 5 cartas). `dead` = cartas conocidas además de la mano y la mesa.
+    pub fn hand_strength(&self, hero: &[MyCard; 2], board: &[MyCard], dead: CardSet) -> Option<HandStrength> {
+        if !(3..=5).contains(&board.len()) {
+            return None;
+        }
+        let board_set: CardSet = board.iter().copied().collect();
+        let known = dead.union(board_set).union(CardSet::from(hero));
+        let hero_rank = self.rank(board_set.union(CardSet::from(hero)));
+
+        let deck: Vec<MyCard> = known.complement().iter().collect();
+        let mut strength = HandStrength::default();
+        let mut better: Vec<BetterHand> = Vec::new();
+        for (i, &a) in deck.iter().enumerate() {
+            for &b in &deck[i + 1..] {
+                let combo = [a, b];
+                let rank = self.rank(board_set.union(CardSet::from(&combo)));
+                strength.live_combos += 1;
+                match rank.cmp(&hero_rank) {
+                    Ordering::Less => strength.beaten += 1,
+                    Ordering::Equal => strength.tied += 1,
+                    Ordering::Greater => match better.iter_mut().find(|h| h.rank == rank) {
+                        Some(hand) => hand.combos.push(combo),
+                        None => better.push(BetterHand { rank, combos: vec![combo] }),
+                    },
+                }
+            }
+        }
+        better.sort_by_key(|hand| Reverse(hand.rank));
+        strength.nut_rank = better.len() + 1;
+        strength.better = better;
+        Some(strength)
+    }
+}
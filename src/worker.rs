@@ -0,0 +1,275 @@
+use crate::card::{CardSet, MyCard};
+use crate::equity::{CalcMode, EquityEngine, EquityReport, Progress, RangeEquityReport, Spot};
+use crate::heatmap::NextCardReport;
+use crate::iso::{self, SuitPermutation};
+use crate::range::Range;
+use crate::showdown::{SeatHand, ShowdownReport};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+
+// --- CÁLCULO EN SEGUNDO PLANO ---
+
+// Cálculo en curso en otro hilo
+struct Job<K, R> {
+    key: K,
+    progress: Arc<Progress>,
+    result: Receiver<Option<R>>,
+}
+
+// Lanza un cálculo sólo cuando cambia su clave (cartas, rango, modo...) y guarda el último resultado.
+// Si llega un pedido nuevo mientras otro está corriendo, el viejo se cancela.
+struct Background<K, R> {
+    job: Option<Job<K, R>>,
+    last: Option<(K, Arc<R>)>,
+}
+
+impl<K: Clone + PartialEq, R: Send + 'static> Background<K, R> {
+    fn new() -> Self {
+        Self { job: None, last: None }
+    }
+
+    // Pide el cálculo de `key`. No hace nada si ya está calculado o en curso.
+    fn request(&mut self, key: &K, compute: impl FnOnce(&Progress) -> Option<R> + Send + 'static) {
+        if self.is_last(key) {
+            self.cancel();
+            return;
+        }
+        if matches!(&self.job, Some(job) if job.key == *key) {
+            return;
+        }
+
+        self.cancel();
+        let (tx, rx) = mpsc::channel();
+        let progress = Arc::new(Progress::default());
+        let job_progress = Arc::clone(&progress);
+        thread::spawn(move || {
+            // Si nadie espera el resultado (pedido reemplazado) simplemente se descarta
+            let _ = tx.send(compute(&job_progress));
+        });
+        self.job = Some(Job { key: key.clone(), progress, result: rx });
+    }
+
+    fn cancel(&mut self) {
+        if let Some(job) = self.job.take() {
+            job.progress.cancel();
+        }
+    }
+
+    fn poll(&mut self) -> bool {
+        let Some(job) = &self.job else { return false };
+        match job.result.try_recv() {
+            Ok(Some(report)) => {
+                let job = self.job.take().expect("job presente");
+                self.last = Some((job.key, Arc::new(report)));
+                true
+            }
+            Ok(None) | Err(TryRecvError::Disconnected) => {
+                self.job = None;
+                false
+            }
+            Err(TryRecvError::Empty) => false,
+        }
+    }
+
+    fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    fn progress(&self) -> f32 {
+        self.job.as_ref().map_or(1.0, |job| job.progress.fraction())
+    }
+
+    // true si `key` es la clave del último resultado terminado
+    fn is_last(&self, key: &K) -> bool {
+        matches!(&self.last, Some((last_key, _)) if last_key == key)
+    }
+
+    fn last(&self) -> Option<Arc<R>> {
+        self.last.as_ref().map(|(_, report)| Arc::clone(report))
+    }
+}
+
+impl<K, R> Drop for Background<K, R> {
+    fn drop(&mut self) {
+        if let Some(job) = self.job.take() {
+            job.progress.cancel();
+        }
+    }
+}
+
+// Equity del hero contra el rango/villano.
+// Trabaja con la forma canónica del Spot: cambiar sólo los palos (As 8d Qc -> Ah 8s Qd) reutiliza el resultado.
+pub struct EquityWorker {
+    engine: Arc<EquityEngine>,
+    background: Background<(Spot, CalcMode), EquityReport>, // En palos canónicos
+    job_perm: SuitPermutation,    // Del último Spot pedido a su forma canónica (el del cálculo en curso)
+    result_perm: SuitPermutation, // Del Spot del último resultado terminado a su forma canónica
+}
+
+impl EquityWorker {
+    pub fn new(engine: Arc<EquityEngine>) -> Self {
+        Self { engine, background: Background::new(), job_perm: SuitPermutation::identity(), result_perm: SuitPermutation::identity() }
+    }
+
+    // Pide el cálculo de `spot`. No hace nada si ya está calculado o en curso.
+    pub fn request(&mut self, spot: &Spot, mode: CalcMode) {
+        let (spot, perm) = iso::canonical(spot);
+        let key = (spot, mode);
+        // El último resultado también vale para este Spot: se muestra con sus palos
+        if self.background.is_last(&key) {
+            self.result_perm = perm;
+        }
+        self.job_perm = perm;
+        let engine = Arc::clone(&self.engine);
+        let job_spot = key.0.clone();
+        self.background.request(&key, move |progress| engine.calculate_with_progress(&job_spot, mode, progress));
+    }
+
+    // Cancela el cálculo en curso (si hay)
+    pub fn cancel(&mut self) {
+        self.background.cancel();
+    }
+
+    // Recoge el resultado si el hilo terminó. Devuelve true si hay un resultado nuevo.
+    pub fn poll(&mut self) -> bool {
+        let finished = self.background.poll();
+        if finished {
+            self.result_perm = self.job_perm;
+        }
+        finished
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.background.is_running()
+    }
+
+    // Avance del cálculo en curso (0.0..=1.0)
+    pub fn progress(&self) -> f32 {
+        self.background.progress()
+    }
+
+    // Último resultado terminado (puede ser de cartas anteriores si hay un cálculo en curso),
+    // con los palos del Spot para el que se calculó
+    pub fn report(&self) -> Option<Arc<EquityReport>> {
+        let report = self.background.last()?;
+        if self.result_perm.is_identity() {
+            Some(report)
+        } else {
+            Some(Arc::new(report.permuted(self.result_perm.inverse())))
+        }
+    }
+}
+
+// Clave de un showdown: jugadores, mesa, cartas muertas y modo
+type ShowdownKey = (Vec<SeatHand>, Vec<MyCard>, CardSet, CalcMode);
+
+// Showdown entre varios jugadores (manos a la vista o rangos)
+pub struct ShowdownWorker {
+    engine: Arc<EquityEngine>,
+    background: Background<ShowdownKey, ShowdownReport>,
+}
+
+impl ShowdownWorker {
+    pub fn new(engine: Arc<EquityEngine>) -> Self {
+        Self { engine, background: Background::new() }
+    }
+
+    // Pide el showdown. No hace nada si ya está calculado o en curso.
+    pub fn request(&mut self, seats: &[SeatHand], board: &[MyCard], dead: CardSet, mode: CalcMode) {
+        let key = (seats.to_vec(), board.to_vec(), dead, mode);
+        let engine = Arc::clone(&self.engine);
+        let (job_seats, job_board) = (key.0.clone(), key.1.clone());
+        self.background.request(&key, move |progress| engine.showdown(&job_seats, &job_board, dead, mode, progress));
+    }
+
+    pub fn poll(&mut self) -> bool {
+        self.background.poll()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.background.is_running()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.background.progress()
+    }
+
+    pub fn report(&self) -> Option<Arc<ShowdownReport>> {
+        self.background.last()
+    }
+}
+
+// Clave de rango vs rango: rango del hero, rango del villano, mesa, cartas muertas y modo
+type RangeKey = (Range, Range, Vec<MyCard>, CardSet, CalcMode);
+
+// Equity de un rango de hero contra el rango del villano
+pub struct RangeWorker {
+    engine: Arc<EquityEngine>,
+    background: Background<RangeKey, RangeEquityReport>,
+}
+
+impl RangeWorker {
+    pub fn new(engine: Arc<EquityEngine>) -> Self {
+        Self { engine, background: Background::new() }
+    }
+
+    // Pide el cálculo. No hace nada si ya está calculado o en curso.
+    pub fn request(&mut self, hero: &Range, villain: &Range, board: &[MyCard], dead: CardSet, mode: CalcMode) {
+        let key = (hero.clone(), villain.clone(), board.to_vec(), dead, mode);
+        let engine = Arc::clone(&self.engine);
+        let (job_hero, job_villain, job_board) = (key.0.clone(), key.1.clone(), key.2.clone());
+        self.background.request(&key, move |progress| engine.range_vs_range(&job_hero, &job_villain, &job_board, dead, mode, progress));
+    }
+
+    pub fn poll(&mut self) -> bool {
+        self.background.poll()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.background.is_running()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.background.progress()
+    }
+
+    pub fn report(&self) -> Option<Arc<RangeEquityReport>> {
+        self.background.last()
+    }
+}
+
+// Equity del hero después de cada turn/river posible (mapa de calor)
+pub struct NextCardWorker {
+    engine: Arc<EquityEngine>,
+    background: Background<Spot, NextCardReport>,
+}
+
+impl NextCardWorker {
+    pub fn new(engine: Arc<EquityEngine>) -> Self {
+        Self { engine, background: Background::new() }
+    }
+
+    // Pide el cálculo de `spot`. No hace nada si ya está calculado o en curso.
+    pub fn request(&mut self, spot: &Spot) {
+        let engine = Arc::clone(&self.engine);
+        let job_spot = spot.clone();
+        self.background.request(spot, move |progress| engine.next_card_equities(&job_spot, progress));
+    }
+
+    pub fn poll(&mut self) -> bool {
+        self.background.poll()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.background.is_running()
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.background.progress()
+    }
+
+    pub fn report(&self) -> Option<Arc<NextCardReport>> {
+        self.background.last()
+    }
+}
@@ -0,0 +1,31 @@
+// Ayudas compartidas por los tests: cartas, manos, rangos y Spots a partir de texto.
+// Cada archivo de tests usa sólo una parte.
+#![allow(dead_code)]
+
+use poker_solver::{CardSet, MyCard, Range, Spot};
+
+pub fn cards(text: &str) -> Vec<MyCard> {
+    text.split_whitespace().map(|c| c.parse().expect("carta válida")).collect()
+}
+
+pub fn hand(text: &str) -> [MyCard; 2] {
+    let c = cards(text);
+    [c[0], c[1]]
+}
+
+pub fn range(text: &str) -> Range {
+    text.parse().expect("rango válido")
+}
+
+// Spot con las cartas del hero, el villano y la mesa como muertas
+pub fn spot(hero: &str, villain: &str, board: &str, range_text: &str) -> Spot {
+    let (hero, villain, board) = (cards(hero), cards(villain), cards(board));
+    Spot {
+        dead: hero.iter().chain(&villain).chain(&board).copied().collect::<CardSet>(),
+        hero,
+        villain,
+        board,
+        range: range(range_text),
+        others: Vec::new(),
+    }
+}
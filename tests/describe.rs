@@ -0,0 +1,28 @@
+// Texto de cada mano en los dos idiomas
+
+mod common;
+
+use common::cards;
+use poker_solver::{describe, HandEvaluator, Language};
+
+#[test]
+fn describes_hands_in_both_languages() {
+    let evaluator = HandEvaluator::new();
+    let cases = [
+        // La rueda es escalera al Cinco, no al As
+        ("Ah 2c 3d 4s 5h", "Escalera al Cinco", "Straight, Five high"),
+        ("Qh Jc Td 9s 8h", "Escalera a la Dama", "Straight, Queen high"),
+        ("Jh 9h 7h 4h 2h", "Color a la Jota (9-7-4-2)", "Flush, Jack high (9-7-4-2)"),
+        ("Ah Kh Qh Jh Th", "Escalera real", "Royal flush"),
+        ("5d 4d 3d 2d Ad", "Escalera de color al Cinco", "Straight flush, Five high"),
+        ("Ks Kd 8c 8h 3s 2d Ac", "Doble pareja, Reyes y Ochos, kicker A", "Two pair, Kings and Eights, A kicker"),
+        // Con tres parejas la tercera sólo aporta el kicker
+        ("Ks Kd 8c 8h 3s 3d 2c", "Doble pareja, Reyes y Ochos, kicker 3", "Two pair, Kings and Eights, 3 kicker"),
+        ("Qs Qd Qc 4h 4s", "Full de Damas y Cuatros", "Full house, Queens full of Fours"),
+    ];
+    for (hand, spanish, english) in cases {
+        let rank = evaluator.evaluate_cards(&cards(hand));
+        assert_eq!(describe(rank, Language::Spanish), spanish, "{}", hand);
+        assert_eq!(describe(rank, Language::English), english, "{}", hand);
+    }
+}
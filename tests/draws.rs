@@ -0,0 +1,64 @@
+// Proyectos en mesas concretas: tipo de escalera, color nuts o no, combo, backdoors y manos ya hechas
+
+mod common;
+
+use common::{cards, hand};
+use poker_solver::{DrawKind, Draws};
+
+fn draws(hole: &str, board: &str) -> Draws {
+    Draws::classify(&hand(hole), &cards(board))
+}
+
+#[test]
+fn wheel_gutshot() {
+    // A-2-3-5: sólo el 4 completa la rueda
+    let d = draws("Ah 3c", "2d 5s 9h");
+    assert!(d.gutshot && !d.open_ended);
+    // 9-8 con 7-6: el 5 y el T completan
+    let d = draws("9c 8d", "7h 6s 2c");
+    assert!(d.open_ended && !d.gutshot);
+}
+
+#[test]
+fn nut_and_non_nut_flush_draws() {
+    let nut = draws("Ah 5h", "Kh 7h 2c");
+    assert!(nut.flush_draw && nut.nut_flush_draw);
+    let second = draws("Qh Jh", "Kh 7h 2c");
+    assert!(second.flush_draw && !second.nut_flush_draw);
+    // Con el As en la mesa la nuts es la K
+    let king = draws("Kh 5h", "Ah 7h 2c");
+    assert!(king.nut_flush_draw);
+    // Cuatro del palo en la mesa y ninguna en la mano: no es proyecto del hero
+    assert!(!draws("Ac Kd", "Qh 7h 2h 3h").flush_draw);
+}
+
+#[test]
+fn combo_draw() {
+    let d = draws("9h 8h", "7h 6c 2h");
+    assert!(d.flush_draw && d.open_ended && d.is_combo_draw());
+    assert!(d.has(DrawKind::ComboDraw));
+    assert_eq!(d.labels()[0], DrawKind::ComboDraw.name());
+    assert!(!draws("Ah 5h", "Kh 7h 2c").is_combo_draw());
+}
+
+#[test]
+fn backdoors_only_on_the_flop() {
+    let flop = draws("Ah Kh", "Qh 7c 2d");
+    assert!(flop.backdoor_flush && flop.backdoor_straight);
+    assert!(flop.has(DrawKind::Backdoor));
+    let turn = draws("Ah Kh", "Qh 7c 2d 3s");
+    assert!(!turn.backdoor_flush && !turn.backdoor_straight);
+    assert!(!turn.has(DrawKind::Backdoor));
+}
+
+#[test]
+fn no_draw_once_made() {
+    // Color hecho: ya no es proyecto de color
+    let flush = draws("9h 8h", "7h 6h 2h");
+    assert!(!flush.flush_draw && !flush.nut_flush_draw);
+    // Escalera hecha: ya no hay proyecto de escalera
+    let straight = draws("9c 8d", "7h 6s 5c");
+    assert!(!straight.open_ended && !straight.gutshot && !straight.backdoor_straight);
+    // En el river no quedan proyectos
+    assert!(draws("Ah 5h", "Kh 7h 2c 3d 9s").is_empty());
+}
@@ -0,0 +1,50 @@
+// Duelo por carta: los totales coinciden con el showdown y cada runout se cuenta una vez por carta que incluye
+
+mod common;
+
+use common::{cards, hand};
+use poker_solver::{CalcMode, CardSet, EquityEngine, Outcome, Progress, SeatHand};
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{} vs {}", a, b);
+}
+
+#[test]
+fn totals_match_showdown() {
+    let engine = EquityEngine::new();
+    for board in ["Qh 7h 2c", "Qh 7h 2c 9s"] {
+        let duel = engine.duel(&hand("Ah Kh"), &hand("Qs Qd"), &cards(board), CardSet::empty());
+        let seats = [SeatHand::Cards(hand("Ah Kh")), SeatHand::Cards(hand("Qs Qd"))];
+        let showdown = engine.showdown(&seats, &cards(board), CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+        assert_close(duel.totals.total(), showdown.showdowns);
+        assert_close(duel.totals.equity(), showdown.seats[0].equity);
+        assert_close(duel.totals.wins / duel.totals.total(), showdown.seats[0].win);
+        assert_close(duel.totals.ties / duel.totals.total(), showdown.seats[0].tie);
+    }
+}
+
+#[test]
+fn next_cards_add_up_to_missing_times_totals() {
+    let engine = EquityEngine::new();
+    for (board, missing, runouts_per_card) in [("Qh 7h 2c", 2.0, 44.0), ("Qh 7h 2c 9s", 1.0, 1.0)] {
+        let duel = engine.duel(&hand("Ah Kh"), &hand("Qs Qd"), &cards(board), CardSet::empty());
+        assert_eq!(duel.next_cards.len(), 52 - 4 - cards(board).len());
+        let mut sum = Outcome::default();
+        for (_, outcome) in &duel.next_cards {
+            assert_close(outcome.total(), runouts_per_card);
+            sum.wins += outcome.wins;
+            sum.losses += outcome.losses;
+            sum.ties += outcome.ties;
+        }
+        assert_close(sum.wins, missing * duel.totals.wins);
+        assert_close(sum.losses, missing * duel.totals.losses);
+        assert_close(sum.ties, missing * duel.totals.ties);
+    }
+}
+
+#[test]
+fn river_has_no_next_cards() {
+    let duel = EquityEngine::new().duel(&hand("Ah Kh"), &hand("Qs Qd"), &cards("Qh 7h 2c 9s 3h"), CardSet::empty());
+    assert!(duel.next_cards.is_empty());
+    assert_eq!(duel.totals.wins, 1.0); // Color al As
+}
@@ -0,0 +1,109 @@
+// Validación del evaluador propio contra poker::Evaluator (la referencia)
+
+use poker::{Eval, EvalClass, Evaluator};
+use poker_solver::{CardSet, HandCategory, HandEvaluator, HandRank, MyCard};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::collections::HashMap;
+
+fn deck() -> Vec<MyCard> {
+    (0..52).map(MyCard::from_index).collect()
+}
+
+fn reference(evaluator: &Evaluator, cards: &[MyCard]) -> Eval {
+    let cards: Vec<poker::Card> = cards.iter().map(|c| format!("{}{}", c.rank_char(), c.suit_char()).parse().expect("carta válida")).collect();
+    evaluator.evaluate(&cards).expect("mano válida")
+}
+
+fn same_category(category: HandCategory, class: EvalClass) -> bool {
+    matches!(
+        (category, class),
+        (HandCategory::HighCard, EvalClass::HighCard { .. })
+            | (HandCategory::Pair, EvalClass::Pair { .. })
+            | (HandCategory::TwoPair, EvalClass::TwoPair { .. })
+            | (HandCategory::ThreeOfAKind, EvalClass::ThreeOfAKind { .. })
+            | (HandCategory::Straight, EvalClass::Straight { .. })
+            | (HandCategory::Flush, EvalClass::Flush { .. })
+            | (HandCategory::FullHouse, EvalClass::FullHouse { .. })
+            | (HandCategory::FourOfAKind, EvalClass::FourOfAKind { .. })
+            | (HandCategory::StraightFlush, EvalClass::StraightFlush { .. })
+    )
+}
+
+// Todas las manos de 5 cartas: cada valor nuestro corresponde a un único valor de la referencia
+// y el orden entre valores es el mismo
+#[test]
+fn matches_reference_on_all_five_card_hands() {
+    let ours = HandEvaluator::new();
+    let theirs = Evaluator::new();
+    let deck = deck();
+    let mut classes: HashMap<HandRank, Eval> = HashMap::new();
+
+    for a in 0..52 {
+        for b in a + 1..52 {
+            for c in b + 1..52 {
+                for d in c + 1..52 {
+                    for e in d + 1..52 {
+                        let hand = [deck[a], deck[b], deck[c], deck[d], deck[e]];
+                        let rank = ours.evaluate_cards(&hand);
+                        let eval = *classes.entry(rank).or_insert_with(|| reference(&theirs, &hand));
+                        assert_eq!(eval, reference(&theirs, &hand), "{hand:?}");
+                    }
+                }
+            }
+        }
+    }
+
+    // 7462 manos distintas en póker de 5 cartas
+    assert_eq!(classes.len(), 7462);
+    let mut sorted: Vec<(HandRank, Eval)> = classes.into_iter().collect();
+    sorted.sort();
+    for pair in sorted.windows(2) {
+        assert!(pair[0].1 < pair[1].1, "orden distinto: {:?} vs {:?}", pair[0], pair[1]);
+    }
+    for (rank, eval) in &sorted {
+        assert!(same_category(rank.category(), eval.class()), "{rank:?} vs {eval}");
+    }
+}
+
+// Manos de 7 cartas al azar: misma categoría y mismo resultado al compararlas de a pares
+#[test]
+fn matches_reference_on_random_seven_card_hands() {
+    let ours = HandEvaluator::new();
+    let theirs = Evaluator::new();
+    let mut deck = deck();
+    let mut rng = StdRng::seed_from_u64(14);
+    let mut previous: Option<(HandRank, Eval)> = None;
+
+    for _ in 0..200_000 {
+        let (hand, _) = deck.partial_shuffle(&mut rng, 7);
+        let rank = ours.evaluate(hand.iter().copied().collect::<CardSet>());
+        let eval = reference(&theirs, hand);
+        assert!(same_category(rank.category(), eval.class()), "{hand:?}: {rank:?} vs {eval}");
+        if let Some((prev_rank, prev_eval)) = previous {
+            assert_eq!(rank.cmp(&prev_rank), eval.cmp(&prev_eval), "{hand:?}");
+        }
+        previous = Some((rank, eval));
+    }
+}
+
+// Con 6 cartas también (turn): la mejor de 5 dentro de las 6
+#[test]
+fn matches_reference_on_random_six_card_hands() {
+    let ours = HandEvaluator::new();
+    let theirs = Evaluator::new();
+    let mut deck = deck();
+    let mut rng = StdRng::seed_from_u64(6);
+    let mut previous: Option<(HandRank, Eval)> = None;
+
+    for _ in 0..100_000 {
+        let (hand, _) = deck.partial_shuffle(&mut rng, 6);
+        let rank = ours.evaluate_cards(hand);
+        let eval = reference(&theirs, hand);
+        if let Some((prev_rank, prev_eval)) = previous {
+            assert_eq!(rank.cmp(&prev_rank), eval.cmp(&prev_eval), "{hand:?}");
+        }
+        previous = Some((rank, eval));
+    }
+}
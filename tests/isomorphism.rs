@@ -0,0 +1,79 @@
+// Situaciones iguales salvo por los palos deben dar exactamente el mismo resultado
+
+mod common;
+
+use common::spot;
+use poker_solver::iso::{canonical, stabilizer};
+use poker_solver::{CalcMode, EquityEngine, Range, SuitPermutation};
+
+#[test]
+fn isomorphic_boards_share_canonical_form() {
+    let a = spot("Qh Qd", "", "As 8d Qc", "22+, A2s+");
+    let b = spot("Qs Qc", "", "Ah 8s Qd", "22+, A2s+");
+    let (canon_a, _) = canonical(&a);
+    let (canon_b, _) = canonical(&b);
+    assert_eq!(canon_a, canon_b);
+
+    // Un palo distinto en el hero ya no es la misma situación
+    let c = spot("Qh Qs", "", "As 8d Qc", "22+, A2s+");
+    assert_ne!(canonical(&c).0, canon_a);
+}
+
+#[test]
+fn canonical_form_maps_back_to_the_original() {
+    let original = spot("Ah Kh", "", "2h 7h 9c", "22+, AhQh, KsQs:0.5");
+    let (canon, perm) = canonical(&original);
+    assert_eq!(perm.inverse().apply_spot(&canon), SuitPermutation::identity().apply_spot(&original));
+}
+
+#[test]
+fn exhaustive_equity_is_equal_for_all_suit_permutations() {
+    let engine = EquityEngine::new();
+    // Rango con pesos distintos por palo para que la permutación también lo afecte
+    let base = spot("Qh Qd", "", "As 8d Qc", "TT+, AhKh, AK:0.5, KQs");
+    let expected = engine.calculate(&base, CalcMode::Exhaustive);
+
+    for perm in SuitPermutation::all() {
+        let permuted = perm.apply_spot(&base);
+        let report = engine.calculate(&permuted, CalcMode::Exhaustive);
+        assert_eq!(report.totals, expected.totals, "{perm:?}");
+        assert_eq!(report.range_combos, expected.range_combos);
+        assert_eq!(report.combo_outcomes, expected.permuted(perm).combo_outcomes, "{perm:?}");
+        for (r1, row) in report.cells.iter().enumerate() {
+            for (r2, cell) in row.iter().enumerate() {
+                assert_eq!(cell.outcome, expected.cells[r1][r2].outcome, "{perm:?} celda {r1},{r2}");
+            }
+        }
+    }
+}
+
+#[test]
+fn monte_carlo_is_equal_for_isomorphic_spots() {
+    let engine = EquityEngine::new();
+    let mode = CalcMode::MonteCarlo { iterations: 5_000, seed: 3 };
+    let a = engine.calculate(&spot("Ks Kc", "Ah Qh", "2h 7h 9d", ""), mode);
+    let b = engine.calculate(&spot("Kd Kh", "As Qs", "2s 7s 9c", ""), mode);
+    assert_eq!(a.totals, b.totals);
+    assert_eq!(a.std_error, b.std_error);
+}
+
+// Con un flop monocolor y el hero del mismo palo, los otros 3 palos son intercambiables:
+// los combos equivalentes se calculan una sola vez y tienen que coincidir con el duelo 1vs1 contra cada uno
+#[test]
+fn symmetric_combos_match_individual_duels() {
+    let engine = EquityEngine::new();
+    let ranged = spot("Ah Kh", "", "2h 7h 9h", "99, T8s, QJo");
+    assert_eq!(stabilizer(&ranged).len(), 6);
+    let report = engine.calculate(&ranged, CalcMode::Exhaustive);
+
+    for (combo, _) in ranged.live_range_combos() {
+        let mut duel = spot("Ah Kh", "", "2h 7h 9h", "");
+        duel.range = Range::empty();
+        duel.villain = combo.to_vec();
+        duel.dead.insert(combo[0]);
+        duel.dead.insert(combo[1]);
+        let expected = engine.calculate(&duel, CalcMode::Exhaustive).totals;
+        let key = poker_solver::combo_key(&combo);
+        assert_eq!(report.combo_outcomes[&key], expected, "{combo:?}");
+    }
+}
@@ -0,0 +1,67 @@
+// Clasificación de manos hechas: sólo cuenta lo que forma alguna carta de la mano
+
+mod common;
+
+use common::cards;
+use poker_solver::{EquityEngine, MadeHand};
+
+fn made(engine: &EquityEngine, hole: &str, board: &str) -> MadeHand {
+    let hole = cards(hole);
+    engine.made_hand(&[hole[0], hole[1]], &cards(board))
+}
+
+#[test]
+fn unpaired_board() {
+    let engine = EquityEngine::new();
+    assert_eq!(made(&engine, "Ah Qd", "As 8d 3c"), MadeHand::TopPair);
+    assert_eq!(made(&engine, "8h 7d", "As 8d 3c"), MadeHand::MiddlePair);
+    assert_eq!(made(&engine, "Kh Kd", "Qs 8d 3c"), MadeHand::Overpair);
+    assert_eq!(made(&engine, "2h 2d", "Qs 8d 3c"), MadeHand::Underpair);
+    assert_eq!(made(&engine, "Ah 8h", "As 8d 3c"), MadeHand::TwoPair);
+    assert_eq!(made(&engine, "8h 8c", "As 8d 3c"), MadeHand::Set);
+    assert_eq!(made(&engine, "Kh Jd", "As 8d 3c"), MadeHand::HighCard);
+}
+
+#[test]
+fn paired_board() {
+    let engine = EquityEngine::new();
+    // La pareja de la mesa no cuenta: sólo la que forma la mano
+    assert_eq!(made(&engine, "Ah Qd", "7s 7d 2c"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Ah 2d", "7s 7d 2c"), MadeHand::BottomPair);
+    assert_eq!(made(&engine, "Kh Kd", "7s 7d 2c"), MadeHand::Overpair);
+    assert_eq!(made(&engine, "5h 5d", "7s 7d 2c"), MadeHand::MiddlePair);
+    assert_eq!(made(&engine, "Kh Qd", "7s 7d Kc"), MadeHand::TopPair);
+    assert_eq!(made(&engine, "Ah 7h", "7s 7d 2c"), MadeHand::Trips);
+    assert_eq!(made(&engine, "2h 2d", "7s 7d 2c"), MadeHand::FullHouse);
+}
+
+#[test]
+fn double_paired_board() {
+    let engine = EquityEngine::new();
+    assert_eq!(made(&engine, "Ah Qd", "7s 7d 2c 2h"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Kh Kd", "7s 7d 2c 2h"), MadeHand::Overpair);
+    assert_eq!(made(&engine, "Ah 9d", "7s 7d 2c 2h 9c"), MadeHand::TopPair);
+    // La tercera pareja no juega: la mano sigue siendo la doble pareja de la mesa
+    assert_eq!(made(&engine, "3h 3d", "9s 9d 7c 7h"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Ah 7h", "7s 7d 2c 2h"), MadeHand::FullHouse);
+}
+
+#[test]
+fn trips_board() {
+    let engine = EquityEngine::new();
+    assert_eq!(made(&engine, "Ah Kd", "7s 7d 7c"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Ah Kd", "7s 7d 7c 7h"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Ah Ad", "7s 7d 7c"), MadeHand::FullHouse);
+    assert_eq!(made(&engine, "Ah 7h", "7s 7d 7c"), MadeHand::Quads);
+    // Full entero en la mesa que la mano no mejora
+    assert_eq!(made(&engine, "Ah Kd", "7s 7d 7c 2h 2d"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Ah 2c", "7s 7d 7c 2h 2d"), MadeHand::HighCard);
+}
+
+#[test]
+fn river_board_plays() {
+    let engine = EquityEngine::new();
+    // Escalera en la mesa: sólo cuenta si la mano la mejora
+    assert_eq!(made(&engine, "Ah Kd", "9s 8d 7c 6h 5d"), MadeHand::HighCard);
+    assert_eq!(made(&engine, "Th Kd", "9s 8d 7c 6h 5d"), MadeHand::Straight);
+}
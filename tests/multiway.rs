@@ -0,0 +1,24 @@
+// Varios rivales con rango: el Monte Carlo tiene que coincidir con el showdown exacto
+
+mod common;
+
+use common::{cards, hand, range, spot};
+use poker_solver::{CalcMode, CardSet, EquityEngine, Progress, SeatHand, Spot};
+
+#[test]
+fn monte_carlo_matches_exact_showdown() {
+    // Los AA de los dos rivales chocan seguido: el reparto tiene que respetar los pesos conjuntos
+    let engine = EquityEngine::new();
+    let seats = [SeatHand::Cards(hand("Jc Jd")), SeatHand::Range(range("AA, 72o")), SeatHand::Range(range("AA, 63o"))];
+    let exact = engine.showdown(&seats, &cards("2c 5d 8h 9s Kc"), CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+    assert!(!exact.sampled);
+    assert!((exact.seats[0].equity - 0.4500).abs() < 5e-5, "{:?}", exact.seats);
+
+    let spot = Spot { others: vec![range("AA, 63o")], ..spot("Jc Jd", "", "2c 5d 8h 9s Kc", "AA, 72o") };
+    for mode in [CalcMode::Exhaustive, CalcMode::MonteCarlo { iterations: 100_000, seed: 3 }] {
+        let report = engine.calculate(&spot, mode);
+        let std_error = report.std_error.expect("con varios rivales se muestrea");
+        let equity = report.totals.equity();
+        assert!((equity - exact.seats[0].equity).abs() < 4.0 * std_error, "{} vs {} (error {})", equity, exact.seats[0].equity, std_error);
+    }
+}
@@ -0,0 +1,62 @@
+// Outs contados a mano: contra una mano concreta, contra un rango (outs manchadas) y con cartas de aliados
+
+mod common;
+
+use common::{cards, spot};
+use poker_solver::{CardSet, EquityEngine, HandCategory, OutsReport, Spot};
+
+fn outs(spot: &Spot) -> OutsReport {
+    EquityEngine::new().outs(spot).expect("hay mano y flop")
+}
+
+fn out_cards(report: &OutsReport) -> Vec<String> {
+    let mut names: Vec<String> = report.outs.iter().map(|o| o.card.display_text()).collect();
+    names.sort();
+    names
+}
+
+fn sorted(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = cards(text).iter().map(|c| c.display_text()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn overcards_against_a_pair() {
+    // AK contra JJ: cualquier As o Rey (3 + 3) pasa por delante
+    let report = outs(&spot("Ah Kh", "Jc Jd", "8s 7c 2d", ""));
+    assert!(report.is_behind());
+    assert_eq!(report.unseen, 45);
+    assert_eq!(out_cards(&report), sorted("As Ac Ad Ks Kc Kd"));
+    assert_eq!(report.clean_count(), 6);
+    assert!(report.outs.iter().all(|o| o.category == HandCategory::Pair && o.equity == 1.0));
+    assert!((report.hit_chance() - 6.0 / 45.0).abs() < 1e-12);
+}
+
+#[test]
+fn flush_out_that_fills_a_boat_is_tainted() {
+    // Proyecto de color nuts + overcards contra QQ o un set de doses: el 2h da color, pero 2d2s liga póker
+    let report = outs(&spot("Ah Kh", "", "9h 5h 2c", "QQ, 22"));
+    assert_eq!(report.equity, 0.0);
+    assert_eq!(out_cards(&report), sorted("2h 3h 4h 6h 7h 8h Th Jh Qh As Ac Ad Ks Kc Kd"));
+    for out in &report.outs {
+        if out.card == cards("2h")[0] {
+            // Quedan 6 QQ y un 22: el hero gana 6 de 7 y el 22 que mejora le gana
+            assert!((out.tainted - 1.0 / 7.0).abs() < 1e-12);
+            assert!((out.equity - 6.0 / 7.0).abs() < 1e-12);
+        } else {
+            assert!(out.is_clean(), "{:?}", out);
+        }
+    }
+    assert_eq!(report.clean_count(), 14);
+}
+
+#[test]
+fn friends_cards_are_dead() {
+    // Un aliado con As Kc se lleva dos de las outs y dos cartas del mazo
+    let base = spot("Ah Kh", "Jc Jd", "8s 7c 2d", "");
+    let with_friend = Spot { dead: base.dead.union(cards("As Kc").into_iter().collect::<CardSet>()), ..base };
+    let report = outs(&with_friend);
+    assert_eq!(report.unseen, 43);
+    assert_eq!(out_cards(&report), sorted("Ac Ad Ks Kd"));
+}
@@ -0,0 +1,63 @@
+// Modo exhaustivo antes del flop: exacto (tabla o todas las mesas); estimación sólo con cartas muertas extra
+
+mod common;
+
+use common::{cards, hand, spot};
+use poker_solver::{CalcMode, CardSet, EquityEngine, Progress, Range, SeatHand, Spot};
+
+#[test]
+fn fixed_villain_is_exact() {
+    let engine = EquityEngine::new();
+    let report = engine.calculate(&spot("Ah Ad", "Kh Kd", "", ""), CalcMode::Exhaustive);
+    // Valor exacto de referencia: 82.64%
+    assert!((report.totals.equity() - 0.8264).abs() < 5e-5, "{}", report.totals.equity());
+    assert_eq!(report.std_error, None);
+
+    // Igual que el showdown, que también recorre las C(48,5) mesas
+    let seats = [SeatHand::Cards(hand("Ah Ad")), SeatHand::Cards(hand("Kh Kd"))];
+    let showdown = engine.showdown(&seats, &[], CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+    assert!(!showdown.sampled);
+    assert!((report.totals.equity() - showdown.seats[0].equity).abs() < 1e-12);
+    assert!((report.totals.win_pct() as f64 / 100.0 - showdown.seats[0].win).abs() < 1e-6);
+}
+
+#[test]
+fn range_is_exact() {
+    // Sin cartas muertas extra cada combo sale de la tabla: valor exacto de AA contra cualquier mano (85.20%)
+    let any_hand = Spot { range: Range::full(), ..spot("Ah Ad", "", "", "") };
+    let report = EquityEngine::new().calculate(&any_hand, CalcMode::Exhaustive);
+    assert_eq!(report.std_error, None);
+    assert!((report.totals.equity() - 0.8520).abs() < 5e-5, "{}", report.totals.equity());
+}
+
+#[test]
+fn table_matches_showdown() {
+    // Manos que comparten palos o rangos: la tabla guarda una mano por celda y renombra los palos
+    let engine = EquityEngine::new();
+    for (hero, villain) in [("Ks Ah", "Qs Qh"), ("7d 2c", "As 2d"), ("Jh Th", "9h 8h"), ("5c 5d", "5h 5s")] {
+        let report = engine.calculate(&spot(hero, villain, "", ""), CalcMode::Exhaustive);
+        let seats = [SeatHand::Cards(hand(hero)), SeatHand::Cards(hand(villain))];
+        let showdown = engine.showdown(&seats, &[], CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+        assert!((report.totals.equity() - showdown.seats[0].equity).abs() < 1e-12, "{} vs {}", hero, villain);
+        assert!((report.totals.tie_pct() as f64 / 100.0 - showdown.seats[0].tie).abs() < 1e-6, "{} vs {}", hero, villain);
+    }
+}
+
+#[test]
+fn dead_cards_fall_back_to_boards() {
+    // Con cartas de un aliado fuera del mazo la tabla no vale
+    let engine = EquityEngine::new();
+    let with_friend = |s: Spot| Spot { dead: s.dead.union(cards("Kc Ks").into_iter().collect()), ..s };
+
+    // Contra un villano concreto se recorren todas las mesas que quedan: igual que el showdown
+    let report = engine.calculate(&with_friend(spot("Ah Ad", "Kh Kd", "", "")), CalcMode::Exhaustive);
+    assert_eq!(report.std_error, None);
+    let seats = [SeatHand::Cards(hand("Ah Ad")), SeatHand::Cards(hand("Kh Kd"))];
+    let dead: CardSet = cards("Kc Ks").into_iter().collect();
+    let showdown = engine.showdown(&seats, &[], dead, CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+    assert!((report.totals.equity() - showdown.seats[0].equity).abs() < 1e-12);
+
+    // Contra un rango, la muestra fija de mesas: estimación con error estándar
+    let report = engine.calculate(&with_friend(Spot { range: Range::full(), ..spot("Ah Ad", "", "", "") }), CalcMode::Exhaustive);
+    assert!(report.std_error.is_some());
+}
@@ -0,0 +1,96 @@
+// Notación de rangos: lectura, escritura (la forma más corta) y errores
+
+mod common;
+
+use common::range;
+use poker_solver::{cell_combos, Range};
+
+// Leer lo que se escribe devuelve el mismo rango
+fn assert_round_trip(original: &Range) {
+    let text = original.to_string();
+    assert_eq!(&range(&text), original, "'{}' no vuelve al mismo rango", text);
+}
+
+#[test]
+fn round_trip_of_written_ranges() {
+    for text in [
+        "",
+        "AA",
+        "22+, A2s+, KTo+, QJs, AKo:0.5",
+        "99-55, AJs-A8s, KQo",
+        "AK, AQ:0.25, KQs:50%, JTs:0.75",
+        "AhKh, QsJs:0.5, 7c7d",
+        "T9s-54s, 22+:0.1",
+    ] {
+        assert_round_trip(&range(text));
+    }
+}
+
+#[test]
+fn round_trip_of_mixed_weights() {
+    // Pesos por celda que se repiten en tramos, más combos sueltos dentro de celdas
+    let mut mixed = Range::empty();
+    for r1_idx in 0..13 {
+        for r2_idx in 0..13 {
+            let weight = [0.0, 1.0, 0.5, 0.25, 1.0][(r1_idx * 7 + r2_idx * 3) % 5];
+            mixed.set_cell_weight(r1_idx, r2_idx, weight);
+        }
+    }
+    let combos = cell_combos(0, 1);
+    mixed.set_combo_weight(&combos[0], 0.3);
+    mixed.set_combo_weight(&combos[2], 0.0);
+    assert_round_trip(&mixed);
+    assert_round_trip(&Range::full());
+}
+
+#[test]
+fn writes_the_shortest_form() {
+    let cases = [
+        ("AA, KK, QQ, JJ, TT, 99, 88, 77, 66, 55, 44, 33, 22", "22+"),
+        ("AA, KK", "KK+"),
+        ("55, 66, 77, 88, 99", "99-55"),
+        ("A2s, A3s, A4s, A5s, A6s, A7s, A8s, A9s, ATs, AJs, AQs, AKs", "A2s+"),
+        ("KTo, K9o, K8o", "KTo-K8o"),
+        ("QJs, QJo", "QJ"),
+        ("AhKh, AsKs, AdKd, AcKc", "AKs"),
+        ("AhKh", "AhKh"),
+        ("AKo:50%", "AKo:0.5"),
+        ("AK, AQ:0.25, KQs:0.5", "AK, KQs:0.5, AQ:0.25"),
+    ];
+    for (text, shortest) in cases {
+        assert_eq!(range(text).to_string(), shortest, "forma corta de '{}'", text);
+    }
+}
+
+#[test]
+fn connector_ranges() {
+    assert_eq!(range("T9s-54s"), range("T9s, 98s, 87s, 76s, 65s, 54s"));
+    assert_eq!(range("54s-T9s"), range("T9s-54s"));
+    assert_eq!(range("KJo-97o"), range("KJo, QTo, J9o, T8o, 97o"));
+    assert_eq!(range("AK-JT"), range("AK, KQ, QJ, JT"));
+}
+
+#[test]
+fn later_tokens_override_earlier_ones() {
+    assert_eq!(range("AhKh:0.5, AKs"), range("AKs"));
+    assert_eq!(range("AKs, AhKh:0.5").combo_weight(&cell_combos(0, 1)[2]), 0.5);
+}
+
+#[test]
+fn rejects_invalid_input() {
+    for text in [
+        "A",       // Falta una carta
+        "AKx",     // Sufijo desconocido
+        "AAs",     // Un par no es suited
+        "XY",      // Rangos inválidos
+        "22-A5s",  // Tramos de distinto tipo
+        "AKs-KQo", // Suited con offsuit
+        "T9s-53s", // Conectores con distinto hueco
+        "AKs:1.5", // Peso fuera de 0..1
+        "22+:-1",
+        "AK:120%",
+        "AhAh", // Combo con la misma carta dos veces
+    ] {
+        assert!(text.parse::<Range>().is_err(), "'{}' debería ser inválido", text);
+    }
+}
@@ -0,0 +1,79 @@
+// Rango vs rango (barrido ordenado por mesa) contra el cálculo combo a combo del modo normal
+
+mod common;
+
+use common::{cards, range};
+use poker_solver::{combo_key, CalcMode, CardSet, EquityEngine, Outcome, Progress, Spot};
+
+// Mismo reparto de victorias/derrotas/empates, sin importar la escala
+fn assert_same_shares(a: &Outcome, b: &Outcome, what: &str) {
+    let share = |o: &Outcome| [o.wins / o.total(), o.losses / o.total(), o.ties / o.total(), o.equity()];
+    for (x, y) in share(a).iter().zip(share(b)) {
+        assert!((x - y).abs() < 1e-9, "{}: {:?} vs {:?}", what, a, b);
+    }
+}
+
+// Para cada combo del rango del hero, el modo normal (hero fijo contra el rango del villano) es la referencia
+fn check(hero_text: &str, villain_text: &str, board_text: &str) {
+    let engine = EquityEngine::new();
+    let (hero, villain, board) = (range(hero_text), range(villain_text), cards(board_text));
+    let board_set: CardSet = board.iter().copied().collect();
+    let report = engine
+        .range_vs_range(&hero, &villain, &board, CardSet::empty(), CalcMode::Exhaustive, &Progress::default())
+        .expect("sin cancelar");
+
+    let mut joint = Outcome::default();
+    let mut hero_weight = 0.0;
+    for (combo, weight) in hero.weighted_combos() {
+        if board_set.intersects(CardSet::from(&combo)) {
+            continue;
+        }
+        let spot = Spot {
+            hero: combo.to_vec(),
+            board: board.clone(),
+            dead: board_set.union(CardSet::from(&combo)),
+            range: villain.clone(),
+            ..Spot::default()
+        };
+        let single = engine.calculate(&spot, CalcMode::Exhaustive);
+        let what = format!("{}{} en {}", combo[0].display_text(), combo[1].display_text(), board_text);
+        assert_same_shares(&report.hero.combo_outcomes[&combo_key(&combo)], &single.totals, &what);
+        // Totales: cada combo del hero pesa lo que su peso por la parte del rango del villano que no bloquea
+        let t = single.totals;
+        joint.wins += t.wins * weight;
+        joint.losses += t.losses * weight;
+        joint.ties += t.ties * weight;
+        joint.tie_share += t.tie_share * weight;
+        hero_weight += weight;
+
+        // Lado del villano con un único combo de hero: equity del hero contra cada combo del villano
+        if hero.weighted_combos().len() == 1 {
+            for (key, outcome) in &single.combo_outcomes {
+                assert_same_shares(&report.villain.combo_outcomes[key], outcome, &what);
+            }
+        }
+    }
+    assert!(hero_weight > 0.0);
+    assert_same_shares(&report.hero.totals, &joint, &format!("totales en {}", board_text));
+}
+
+#[test]
+fn single_combo_matches_calculate() {
+    check("AhKh", "22+, A2s+, KTo+, QJs, T9s:0.5", "Kd 7h 2h");
+}
+
+#[test]
+fn overlapping_ranges_match_calculate() {
+    // Los dos rangos comparten combos y cartas (AKs, QQ): los choques se descuentan por carta
+    check("AKs, QQ, JTs:0.5, AhQd", "AKs, QQ+, JTs, AQo:0.25", "Qc 9h 8d");
+}
+
+#[test]
+fn turn_with_weighted_ranges() {
+    check("AA, KK:0.5, 98s", "AK, KK, 99-77:0.75, T9s", "Ah Kc 9s 2d");
+}
+
+#[test]
+fn preflop_uses_exact_pairs() {
+    check("AKs, 22, JTo:0.5", "QQ+, AKo, 76s", "");
+}
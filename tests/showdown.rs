@@ -0,0 +1,119 @@
+// Showdown con las manos a la vista: resultados exactos conocidos y recuento a mano de todas las mesas
+
+mod common;
+
+use common::{cards, hand, range};
+use poker_solver::showdown::MAX_SEATS;
+use poker_solver::{CalcMode, CardSet, EquityEngine, HandEvaluator, MyCard, Progress, SeatHand, ShowdownReport};
+
+fn showdown(hands: &[&str], board: &str, dead: &str) -> ShowdownReport {
+    let seats: Vec<SeatHand> = hands.iter().map(|h| SeatHand::Cards(hand(h))).collect();
+    let dead: CardSet = cards(dead).into_iter().collect();
+    EquityEngine::new().showdown(&seats, &cards(board), dead, CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar")
+}
+
+// Equity de cada mano recorriendo todas las mesas posibles, sin pasar por el motor
+fn brute_force(hands: &[&str], board: &str, dead: &str) -> Vec<f64> {
+    let evaluator = HandEvaluator::new();
+    let hands: Vec<CardSet> = hands.iter().map(|h| CardSet::from(&hand(h))).collect();
+    let board: CardSet = cards(board).into_iter().collect();
+    let known = hands.iter().fold(board.union(cards(dead).into_iter().collect()), |k, h| k.union(*h));
+    let deck: Vec<MyCard> = known.complement().iter().collect();
+
+    let mut equity = vec![0.0; hands.len()];
+    let mut boards = 0.0;
+    let mut deal = |full: CardSet| {
+        let ranks: Vec<_> = hands.iter().map(|h| evaluator.evaluate(full.union(*h))).collect();
+        let best = *ranks.iter().max().expect("al menos un jugador");
+        let winners = ranks.iter().filter(|r| **r == best).count() as f64;
+        for (e, r) in equity.iter_mut().zip(&ranks) {
+            if *r == best {
+                *e += 1.0 / winners;
+            }
+        }
+        boards += 1.0;
+    };
+    // Turn y river (como mucho faltan 2 cartas en estos casos)
+    match 5 - board.len() {
+        0 => deal(board),
+        1 => deck.iter().for_each(|c| deal(board.union([*c].into_iter().collect()))),
+        _ => {
+            for (i, a) in deck.iter().enumerate() {
+                for b in &deck[i + 1..] {
+                    deal(board.union([*a, *b].into_iter().collect()));
+                }
+            }
+        }
+    }
+    equity.iter().map(|e| e / boards).collect()
+}
+
+fn assert_equities(report: &ShowdownReport, expected: &[f64], tolerance: f64) {
+    assert!(!report.sampled);
+    for (seat, e) in report.seats.iter().zip(expected) {
+        assert!((seat.equity - e).abs() < tolerance, "{:?} vs {:?}", report.seats, expected);
+    }
+}
+
+#[test]
+fn aces_vs_kings_preflop() {
+    // Valores de referencia exactos (todas las mesas): 82.64% / 17.36%
+    let report = showdown(&["Ah Ad", "Kh Kd"], "", "");
+    assert_equities(&report, &[0.8264, 0.1736], 5e-5);
+    assert_eq!(report.showdowns, 1_712_304.0); // C(48, 5)
+}
+
+#[test]
+fn split_pot_on_the_board() {
+    let report = showdown(&["Ah Kd", "2c 3c", "7h 7d"], "As Ks Qs Js Ts", "");
+    assert_equities(&report, &[1.0 / 3.0; 3], 1e-12);
+    assert_eq!(report.seats[0].tie, 1.0);
+}
+
+#[test]
+fn matches_brute_force_postflop() {
+    for (hands, board) in [
+        (&["Ah Kh", "Qs Qd"][..], "Qh 7h 2c"),
+        (&["Ah Kh", "Qs Qd", "Jc Tc"][..], "9c 8h 2c"),
+        (&["5s 4s", "Ac Ad", "Kc Kd"][..], "3s 2d Ts 9h"),
+    ] {
+        let expected = brute_force(hands, board, "");
+        assert_equities(&showdown(hands, board, ""), &expected, 1e-12);
+    }
+}
+
+#[test]
+fn dead_cards_are_not_dealt() {
+    // Sin los otros dos ases AA no puede ligar set: cambia la equity y coincide con el recuento
+    let (hands, board) = (&["Ah Ad", "Kh Kd"][..], "Qs 7c 2d");
+    let with_dead = showdown(hands, board, "As Ac");
+    let expected = brute_force(hands, board, "As Ac");
+    assert_equities(&with_dead, &expected, 1e-12);
+    assert!(with_dead.seats[0].equity < showdown(hands, board, "").seats[0].equity);
+    assert_eq!(with_dead.showdowns, 903.0); // C(43, 2)
+}
+
+#[test]
+fn monte_carlo_deals_ranges_jointly() {
+    // AA contra AA choca casi siempre: de los 78 repartos posibles sólo 6 son AA vs AA (empate)
+    let seats = [SeatHand::Range(range("AA, 72o")), SeatHand::Range(range("AA"))];
+    let board = cards("Kc Qd 3h 4s 9c");
+    let engine = EquityEngine::new();
+    let exact = engine.showdown(&seats, &board, CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).expect("sin cancelar");
+    assert!(!exact.sampled);
+    assert!((exact.seats[0].equity - 3.0 / 78.0).abs() < 1e-12);
+    assert!((exact.seats[0].tie - 6.0 / 78.0).abs() < 1e-12);
+
+    let mode = CalcMode::MonteCarlo { iterations: 100_000, seed: 11 };
+    let sampled = engine.showdown(&seats, &board, CardSet::empty(), mode, &Progress::default()).expect("sin cancelar");
+    assert!(sampled.sampled);
+    for (s, e) in sampled.seats.iter().zip(&exact.seats) {
+        assert!((s.equity - e.equity).abs() < 0.005 && (s.tie - e.tie).abs() < 0.005, "{:?} vs {:?}", sampled.seats, exact.seats);
+    }
+}
+
+#[test]
+fn too_many_seats_is_none() {
+    let seats = vec![SeatHand::Range(range("22+")); MAX_SEATS + 1];
+    assert!(EquityEngine::new().showdown(&seats, &[], CardSet::empty(), CalcMode::Exhaustive, &Progress::default()).is_none());
+}
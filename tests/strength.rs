@@ -0,0 +1,43 @@
+// Fuerza relativa: puesto entre las manos posibles, combos a los que gana y manos que le ganan en orden
+
+mod common;
+
+use common::{cards, hand};
+use poker_solver::{CardSet, EquityEngine, HandCategory};
+
+#[test]
+fn set_behind_a_straight_and_a_higher_set() {
+    // Set de Reyes en A-K-T sin color posible: sólo QJ (escalera) y AA (set mayor) le ganan
+    let engine = EquityEngine::new();
+    let strength = engine.hand_strength(&hand("Ks Kc"), &cards("Ac Kd Th"), CardSet::empty()).expect("hay flop");
+    assert_eq!(strength.nut_rank, 3);
+    assert!(!strength.is_nuts());
+    assert_eq!(strength.live_combos, 1081); // C(47, 2)
+    assert_eq!(strength.tied, 0);
+    assert_eq!(strength.beaten, 1081 - 16 - 3);
+    assert!((strength.beats() - 1062.0 / 1081.0).abs() < 1e-12);
+
+    // De mejor a peor: primero la escalera (16 combos de QJ), después el set de Ases (3 combos)
+    let [straight, set] = strength.better.as_slice() else { panic!("dos manos mejores: {:?}", strength.better) };
+    assert_eq!(straight.rank.category(), HandCategory::Straight);
+    assert_eq!(straight.combos.len(), 16);
+    assert_eq!(set.rank.category(), HandCategory::ThreeOfAKind);
+    assert_eq!(set.combos.len(), 3);
+    assert!(straight.rank > set.rank);
+}
+
+#[test]
+fn nuts_and_dead_cards() {
+    let engine = EquityEngine::new();
+    // Con QJ la escalera es la nuts
+    let nuts = engine.hand_strength(&hand("Qs Jd"), &cards("Ac Kd Th"), CardSet::empty()).expect("hay flop");
+    assert!(nuts.is_nuts());
+    assert!(nuts.better.is_empty());
+    // Las cartas muertas sacan combos: sin Ah sólo queda AsAd como set de Ases
+    let dead: CardSet = cards("Ah").into_iter().collect();
+    let strength = engine.hand_strength(&hand("Ks Kc"), &cards("Ac Kd Th"), dead).expect("hay flop");
+    assert_eq!(strength.better[1].combos.len(), 1);
+    assert_eq!(strength.live_combos, 1035); // C(46, 2)
+    // Sin flop no hay fuerza relativa
+    assert!(engine.hand_strength(&hand("Ks Kc"), &[], CardSet::empty()).is_none());
+}
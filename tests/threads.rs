@@ -0,0 +1,67 @@
+// La cantidad de hilos sólo cambia la velocidad: el resultado tiene que ser idéntico bit a bit
+
+mod common;
+
+use common::{cards, hand, range, spot};
+use poker_solver::{CalcMode, CardSet, EquityEngine, EquityReport, Progress, SeatHand, Spot};
+
+fn assert_same_report(a: &EquityReport, b: &EquityReport) {
+    assert_eq!(a.totals, b.totals);
+    assert_eq!(a.combo_outcomes, b.combo_outcomes);
+    assert_eq!(a.range_combos, b.range_combos);
+    assert_eq!(a.std_error, b.std_error);
+    for (row_a, row_b) in a.cells.iter().zip(&b.cells) {
+        for (x, y) in row_a.iter().zip(row_b) {
+            assert_eq!((x.outcome, x.ahead, x.behind, x.even, x.blocked), (y.outcome, y.ahead, y.behind, y.even, y.blocked));
+        }
+    }
+}
+
+fn calculate_with(threads: usize, spot: &Spot, mode: CalcMode) -> EquityReport {
+    let engine = EquityEngine::new();
+    engine.set_threads(threads);
+    engine.calculate(spot, mode)
+}
+
+const MONTE_CARLO: CalcMode = CalcMode::MonteCarlo { iterations: 20_000, seed: 7 };
+
+#[test]
+fn calculate_exhaustive_ignores_threads() {
+    for spot in [
+        spot("Ah Kh", "", "Qh 7h 2c", "22+, A2s+, KTo+, QJs"),
+        spot("Ah Kh", "Qs Qd", "Qh 7h 2c", ""),
+        spot("9s 8s", "", "", "TT+, AQs+, AKo"),
+    ] {
+        assert_same_report(&calculate_with(1, &spot, CalcMode::Exhaustive), &calculate_with(4, &spot, CalcMode::Exhaustive));
+    }
+}
+
+#[test]
+fn calculate_monte_carlo_ignores_threads() {
+    for spot in [
+        spot("Ah Kh", "", "Qh 7h 2c", "22+, A2s+, KTo+, QJs"),
+        Spot { others: vec![range("TT+, AK"), range("22+")], ..spot("Jc Jd", "", "", "22+, A2s+") },
+    ] {
+        assert_same_report(&calculate_with(1, &spot, MONTE_CARLO), &calculate_with(4, &spot, MONTE_CARLO));
+    }
+}
+
+#[test]
+fn showdown_ignores_threads() {
+    let seats = vec![
+        SeatHand::Cards(hand("Ah Ad")),
+        SeatHand::Cards(hand("Kh Kd")),
+        SeatHand::Range(range("QQ+, AKs")),
+    ];
+    let board = cards("2c 7s 9d");
+    for mode in [CalcMode::Exhaustive, MONTE_CARLO] {
+        let run = |threads: usize| {
+            let engine = EquityEngine::new();
+            engine.set_threads(threads);
+            engine.showdown(&seats, &board, CardSet::empty(), mode, &Progress::default()).expect("sin cancelar")
+        };
+        let (one, many) = (run(1), run(4));
+        assert_eq!(one.seats, many.seats);
+        assert_eq!((one.showdowns, one.sampled), (many.showdowns, many.sampled));
+    }
+}